@@ -1,14 +1,82 @@
-use std::io::{self, Write};
+use std::{
+    io::{self, Write},
+    path::Path,
+};
 
 use axiom_sdk::{
     AxiomSdk,
-    build::{BuildSdk, UploadExeArgs},
+    build::{BuildSdk, BuildStatus, ConfigSource, ProgramListQuery, ProgramUpdate, UploadExeArgs},
+    lock::{AxiomLock, LOCK_FILE_NAME, LockSdk},
+    timeline::Timeline,
 };
 use clap::{Parser, Subcommand};
-use comfy_table;
 use eyre::Result;
 
-use crate::{formatting::Formatter, progress::CliProgressCallback};
+use crate::{
+    formatting::Formatter,
+    hooks::HookArgs,
+    interrupt::resume_hint,
+    listing::{Column, ListFilterArgs, build_table, duration, or_dash, select_columns},
+    progress::CliProgressCallback,
+};
+
+const PROGRAM_COLUMNS: [Column<BuildStatus>; 11] = [
+    Column {
+        name: "id",
+        header: "ID",
+        value: |p| or_dash(&p.id),
+    },
+    Column {
+        name: "name",
+        header: "Name",
+        value: |p| or_dash(&p.name),
+    },
+    Column {
+        name: "status",
+        header: "Status",
+        value: |p| or_dash(&p.status),
+    },
+    Column {
+        name: "project",
+        header: "Project",
+        value: |p| or_dash(&p.project_name),
+    },
+    Column {
+        name: "created_by",
+        header: "Created By",
+        value: |p| or_dash(&p.created_by),
+    },
+    Column {
+        name: "created_at",
+        header: "Created At",
+        value: |p| or_dash(&p.created_at),
+    },
+    Column {
+        name: "cells_used",
+        header: "Cells Used",
+        value: |p| p.cells_used.to_string(),
+    },
+    Column {
+        name: "proofs_run",
+        header: "Proofs Run",
+        value: |p| p.proofs_run.to_string(),
+    },
+    Column {
+        name: "num_gpus",
+        header: "Default Num GPUs",
+        value: |p| p.default_num_gpus.to_string(),
+    },
+    Column {
+        name: "labels",
+        header: "Labels",
+        value: |p| or_dash(&p.labels.join(", ")),
+    },
+    Column {
+        name: "duration",
+        header: "Build Duration",
+        value: |p| duration(Timeline::from(p)),
+    },
+];
 
 #[derive(Debug, Parser)]
 #[command(
@@ -34,6 +102,9 @@ enum BuildSubcommand {
         /// Wait for the build to complete
         #[clap(long)]
         wait: bool,
+
+        #[command(flatten)]
+        hooks: HookArgs,
     },
 
     /// List all build programs
@@ -44,6 +115,21 @@ enum BuildSubcommand {
         /// Page size (default: 20)
         #[arg(long, default_value = "20")]
         page_size: u32,
+
+        #[command(flatten)]
+        filters: ListFilterArgs,
+
+        /// Only show programs in this project
+        #[arg(long, value_name = "ID")]
+        project: Option<String>,
+
+        /// Only show programs whose name contains this string
+        #[arg(long, value_name = "NAME")]
+        name: Option<String>,
+
+        /// Also show archived programs
+        #[arg(long)]
+        include_archived: bool,
     },
 
     /// Download build artifacts
@@ -56,6 +142,62 @@ enum BuildSubcommand {
         #[clap(long, value_name = "TYPE", value_parser = ["exe", "elf", "source", "app_exe_commit", "all"])]
         artifact: String,
     },
+
+    /// Download logs for a build
+    Logs {
+        /// The program ID to download logs for
+        #[clap(long, value_name = "ID")]
+        program_id: String,
+
+        /// Print the logs as they are written until the build finishes
+        #[clap(long)]
+        follow: bool,
+    },
+
+    /// Update a program's name, default GPU count or labels
+    Update {
+        /// The program ID to update
+        #[clap(long, value_name = "ID")]
+        program_id: String,
+
+        /// New name for the program
+        #[clap(long, value_name = "NAME")]
+        name: Option<String>,
+
+        /// New default number of GPUs for proofs of this program
+        #[clap(long, value_name = "N")]
+        default_num_gpus: Option<usize>,
+
+        /// Add a label to the program (can be repeated)
+        #[clap(long = "add-label", value_name = "LABEL")]
+        add_labels: Vec<String>,
+
+        /// Remove a label from the program (can be repeated)
+        #[clap(long = "remove-label", value_name = "LABEL")]
+        remove_labels: Vec<String>,
+    },
+
+    /// Archive a program so it no longer shows up in `build list`
+    Archive {
+        /// The program ID to archive
+        #[clap(long, value_name = "ID")]
+        program_id: String,
+
+        /// Unarchive the program instead
+        #[clap(long)]
+        undo: bool,
+    },
+
+    /// Permanently delete a program
+    Delete {
+        /// The program ID to delete
+        #[clap(long, value_name = "ID")]
+        program_id: String,
+
+        /// Don't ask for confirmation
+        #[clap(long)]
+        yes: bool,
+    },
 }
 
 #[derive(Debug, Parser)]
@@ -69,7 +211,7 @@ pub struct BuildArgs {
     bin: Option<String>,
 
     /// Custom program name
-    #[clap(long, value_name = "NAME")]
+    #[clap(long, value_name = "NAME", conflicts_with = "remote")]
     program_name: Option<String>,
 
     /// The project ID to associate with the program
@@ -83,6 +225,72 @@ pub struct BuildArgs {
     /// Specify default_num_gpus for this program
     #[clap(long)]
     default_num_gpus: Option<usize>,
+
+    /// Fail if the build doesn't match the program hash and config recorded in axiom.lock
+    #[clap(long, conflicts_with = "detach")]
+    locked: bool,
+
+    /// Overwrite axiom.lock even if the build's program hash or config differs from it
+    #[clap(long, conflicts_with_all = ["detach", "locked"])]
+    update_lock: bool,
+
+    /// Upload the source tree and build the program on the Axiom Proving Service
+    /// instead of uploading a locally-built ELF + VMEXE
+    #[clap(long)]
+    remote: bool,
+
+    /// Path to an OpenVM TOML configuration file to build with (remote builds only)
+    #[clap(
+        long,
+        value_name = "FILE",
+        requires = "remote",
+        conflicts_with = "config_id"
+    )]
+    config_path: Option<String>,
+
+    /// Comma-separated list of file patterns to exclude from the source archive (e.g. "*.log,temp/*")
+    #[clap(long, value_name = "PATTERNS", requires = "remote")]
+    exclude_files: Option<String>,
+
+    /// Comma-separated list of directories to include even if not tracked by git
+    #[clap(long, value_name = "DIRS", requires = "remote")]
+    include_dirs: Option<String>,
+
+    /// Allow building with uncommitted changes
+    #[clap(long, requires = "remote")]
+    allow_dirty: bool,
+
+    /// Upload the source archive even if it looks like it contains secrets
+    #[clap(long, requires = "remote")]
+    allow_secrets: bool,
+
+    /// Keep the source archive after uploading
+    #[clap(long, requires = "remote")]
+    keep_tarball: bool,
+
+    /// OpenVM Rust toolchain to build the guest with (e.g. "nightly-2025-08-02")
+    #[clap(long, value_name = "TOOLCHAIN", requires = "remote")]
+    openvm_rust_toolchain: Option<String>,
+
+    /// Build the source tree of this git revision (commit, tag or branch) instead of the working tree
+    #[clap(
+        long,
+        value_name = "REV",
+        requires = "remote",
+        conflicts_with_all = ["include_dirs", "vendor_dir"]
+    )]
+    rev: Option<String>,
+
+    /// `cargo vendor` directory, relative to the cargo workspace root, to take dependencies from instead of fetching them
+    #[clap(long, value_name = "DIR", requires = "remote")]
+    vendor_dir: Option<String>,
+
+    /// Fetch dependencies again instead of reusing the cache in ~/.axiom/cargo-cache
+    #[clap(long, requires = "remote")]
+    refresh_fetch_cache: bool,
+
+    #[command(flatten)]
+    hooks: HookArgs,
 }
 
 impl BuildCmd {
@@ -92,45 +300,51 @@ impl BuildCmd {
         let sdk = AxiomSdk::new(config.clone()).with_callback(callback);
 
         match self.command {
-            Some(BuildSubcommand::Status { program_id, wait }) => {
+            Some(BuildSubcommand::Status {
+                program_id,
+                wait,
+                hooks,
+            }) => {
                 if wait {
-                    sdk.wait_for_build_completion(&program_id)
+                    let sdk = sdk.with_completion_hooks(hooks.into());
+                    wait_for_build(&sdk, &program_id)
                 } else {
                     let build_status = sdk.get_build_status(&program_id)?;
                     Self::print_build_status(&build_status);
                     Ok(())
                 }
             }
-            Some(BuildSubcommand::List { page, page_size }) => {
-                let response = sdk.list_programs(Some(page), Some(page_size))?;
+            Some(BuildSubcommand::List {
+                page,
+                page_size,
+                filters,
+                project,
+                name,
+                include_archived,
+            }) => {
+                let columns = select_columns(
+                    filters.columns.as_deref(),
+                    &PROGRAM_COLUMNS,
+                    &["id", "status", "created_at"],
+                )?;
+                let query = ProgramListQuery {
+                    status: filters.status,
+                    created_by: filters.created_by,
+                    created_after: filters.created_after,
+                    created_before: filters.created_before,
+                    project_id: project,
+                    name,
+                    sort: filters.sort,
+                    include_archived,
+                };
+                let response = sdk.list_programs_with_query(Some(page), Some(page_size), &query)?;
 
                 if response.items.is_empty() {
                     println!("No programs found");
                     return Ok(());
                 }
 
-                // Create a new table
-                let mut table = comfy_table::Table::new();
-                table.set_header(["ID", "Status", "Created At"]);
-
-                // Add rows to the table
-                for build_status in response.items {
-                    let get_value = |s: &str| {
-                        if s.is_empty() {
-                            "-".to_string()
-                        } else {
-                            s.to_string()
-                        }
-                    };
-                    let id = get_value(&build_status.id);
-                    let status = get_value(&build_status.status);
-                    let created_at = get_value(&build_status.created_at);
-
-                    table.add_row([id, status, created_at]);
-                }
-
-                // Print the table
-                println!("{table}");
+                println!("{}", build_table(&columns, &response.items));
 
                 let pagination = &response.pagination;
                 println!(
@@ -144,23 +358,113 @@ impl BuildCmd {
                 program_id,
                 artifact,
             }) => sdk.download_program(&program_id, &artifact),
+            Some(BuildSubcommand::Logs { program_id, follow }) => {
+                if follow {
+                    resume_hint(
+                        sdk.follow_build_logs(&program_id),
+                        "build",
+                        &program_id,
+                        &format!("cargo axiom build logs --program-id {program_id} --follow"),
+                    )
+                } else {
+                    sdk.get_build_logs(&program_id)
+                }
+            }
+            Some(BuildSubcommand::Update {
+                program_id,
+                name,
+                default_num_gpus,
+                add_labels,
+                remove_labels,
+            }) => {
+                let labels = if add_labels.is_empty() && remove_labels.is_empty() {
+                    None
+                } else {
+                    let mut labels = sdk.get_build_status(&program_id)?.labels;
+                    labels.retain(|label| !remove_labels.contains(label));
+                    for label in add_labels {
+                        if !labels.contains(&label) {
+                            labels.push(label);
+                        }
+                    }
+                    Some(labels)
+                };
+                if name.is_none() && default_num_gpus.is_none() && labels.is_none() {
+                    eyre::bail!(
+                        "Nothing to update. Pass --name, --default-num-gpus, --add-label or --remove-label."
+                    );
+                }
+
+                sdk.update_program(
+                    &program_id,
+                    ProgramUpdate {
+                        name,
+                        default_num_gpus,
+                        labels,
+                        archived: None,
+                    },
+                )?;
+                println!("✓ Updated program {program_id}");
+                Ok(())
+            }
+            Some(BuildSubcommand::Archive { program_id, undo }) => {
+                if undo {
+                    sdk.unarchive_program(&program_id)?;
+                    println!("✓ Unarchived program {program_id}");
+                } else {
+                    sdk.archive_program(&program_id)?;
+                    println!("✓ Archived program {program_id}");
+                }
+                Ok(())
+            }
+            Some(BuildSubcommand::Delete { program_id, yes }) => {
+                if !yes {
+                    print!("Permanently delete program {program_id} and its proofs? [y/N]: ");
+                    let _ = io::stdout().flush();
+                    let mut input = String::new();
+                    io::stdin().read_line(&mut input)?;
+                    if !matches!(input.trim(), "y" | "Y" | "yes") {
+                        println!("Aborted");
+                        return Ok(());
+                    }
+                }
+                sdk.delete_program(&program_id)?;
+                println!("✓ Deleted program {program_id}");
+                Ok(())
+            }
             None => {
                 let program_dir = std::env::current_dir()?;
 
-                let project_id = {
-                    let cache_path = program_dir.join(".axiom").join("project-id");
-                    match std::fs::read_to_string(&cache_path) {
-                        Ok(contents) => {
-                            let trimmed = contents.trim();
-                            if trimmed.is_empty() {
-                                None
-                            } else {
-                                Some(trimmed.to_string())
-                            }
-                        }
-                        Err(_) => None,
+                let lock = if self.build_args.locked {
+                    let lock = AxiomLock::read(&program_dir)?.ok_or_else(|| {
+                        eyre::eyre!("--locked was passed but no {LOCK_FILE_NAME} was found")
+                    })?;
+                    // A config file is only resolved to a config ID by the server, so
+                    // it is checked against the lock after the build instead.
+                    let config_id = if self.build_args.config_path.is_some() {
+                        None
+                    } else {
+                        self.build_args
+                            .config_id
+                            .as_deref()
+                            .or(config.config_id.as_deref())
+                    };
+                    if let Some(config_id) = config_id
+                        && config_id != lock.config_uuid
+                    {
+                        eyre::bail!(
+                            "Config {} does not match {} in {}",
+                            config_id,
+                            lock.config_uuid,
+                            LOCK_FILE_NAME
+                        );
                     }
+                    Some(lock)
+                } else {
+                    None
                 };
+
+                let project_id = read_cached_project_id(&program_dir);
                 let had_cached_pid = project_id.is_some();
                 let project_name_for_creation = if had_cached_pid {
                     None
@@ -174,15 +478,41 @@ impl BuildCmd {
                     if name.is_empty() { None } else { Some(name) }
                 };
 
-                let args = UploadExeArgs {
-                    config_id: self.build_args.config_id,
-                    project_id,
-                    project_name: project_name_for_creation.clone(),
-                    bin_name: self.build_args.bin,
-                    program_name: self.build_args.program_name,
-                    default_num_gpus: self.build_args.default_num_gpus,
+                let program_id = if self.build_args.remote {
+                    let config_source =
+                        match (self.build_args.config_id, self.build_args.config_path) {
+                            (Some(id), _) => Some(ConfigSource::ConfigId(id)),
+                            (None, Some(path)) => Some(ConfigSource::ConfigPath(path)),
+                            (None, None) => None,
+                        };
+                    let args = axiom_sdk::build::BuildArgs {
+                        config_source,
+                        bin: self.build_args.bin.clone(),
+                        keep_tarball: Some(self.build_args.keep_tarball),
+                        exclude_files: self.build_args.exclude_files,
+                        include_dirs: self.build_args.include_dirs,
+                        project_id,
+                        project_name: project_name_for_creation.clone(),
+                        allow_dirty: self.build_args.allow_dirty,
+                        default_num_gpus: self.build_args.default_num_gpus,
+                        openvm_rust_toolchain: self.build_args.openvm_rust_toolchain,
+                        rev: self.build_args.rev,
+                        allow_secrets: self.build_args.allow_secrets,
+                        vendor_dir: self.build_args.vendor_dir,
+                        refresh_fetch_cache: self.build_args.refresh_fetch_cache,
+                    };
+                    sdk.register_new_program(&program_dir, args)?
+                } else {
+                    let args = UploadExeArgs {
+                        config_id: self.build_args.config_id,
+                        project_id,
+                        project_name: project_name_for_creation.clone(),
+                        bin_name: self.build_args.bin.clone(),
+                        program_name: self.build_args.program_name,
+                        default_num_gpus: self.build_args.default_num_gpus,
+                    };
+                    sdk.upload_exe(&program_dir, args)?
                 };
-                let program_id = sdk.upload_exe(&program_dir, args)?;
 
                 // Always fetch the latest build status to get project ID and print console URL
                 let status = sdk.get_build_status(&program_id)?;
@@ -198,23 +528,53 @@ impl BuildCmd {
 
                 // If we didn't have a cached project ID, try to fetch and cache it now
                 if !had_cached_pid {
-                    let cache_dir = program_dir.join(".axiom");
-                    let cache_path = cache_dir.join("project-id");
-                    if !cache_path.exists() {
-                        if let Err(e) = std::fs::create_dir_all(&cache_dir) {
-                            eprintln!("Warning: failed to create .axiom directory: {e}");
-                        } else if let Err(e) =
-                            std::fs::write(&cache_path, status.project_id.as_bytes())
-                        {
-                            eprintln!("Warning: failed to write project ID cache: {e}");
-                        } else {
-                            println!("✓ Saved project ID {} for future builds", status.project_id);
-                        }
-                    }
+                    cache_project_id(&program_dir, &status.project_id);
                 }
 
                 if !self.build_args.detach {
-                    sdk.wait_for_build_completion(&program_id)
+                    let sdk = sdk.with_completion_hooks(self.build_args.hooks.into());
+                    wait_for_build(&sdk, &program_id)?;
+
+                    if let Some(lock) = lock {
+                        // The hash is only known after the build, so a mismatching
+                        // program is deleted rather than left registered
+                        let status = sdk.get_build_status(&program_id)?;
+                        if let Err(mismatch) = lock.verify_build(&status) {
+                            return match sdk.delete_program(&program_id) {
+                                Ok(()) => Err(mismatch.wrap_err(format!(
+                                    "Deleted program {program_id} because it doesn't match {LOCK_FILE_NAME}"
+                                ))),
+                                Err(e) => Err(mismatch.wrap_err(format!(
+                                    "Program {program_id} doesn't match {LOCK_FILE_NAME} and could not be deleted: {e}"
+                                ))),
+                            };
+                        }
+                        println!("✓ Build matches {LOCK_FILE_NAME}");
+                    } else {
+                        let lock = sdk.create_lock(
+                            &program_dir,
+                            &program_id,
+                            self.build_args.bin.as_deref(),
+                        )?;
+                        let changes = AxiomLock::read(&program_dir)?
+                            .map(|existing| existing.changes(&lock))
+                            .unwrap_or_default();
+                        if !changes.is_empty() {
+                            Formatter::print_warning(&format!(
+                                "The build differs from {LOCK_FILE_NAME}:\n  {}",
+                                changes.join("\n  ")
+                            ));
+                            if !self.build_args.update_lock {
+                                Formatter::print_info(&format!(
+                                    "{LOCK_FILE_NAME} was not changed. Pass --update-lock to record this build"
+                                ));
+                                return Ok(());
+                            }
+                        }
+                        let path = lock.write(&program_dir)?;
+                        println!("✓ Wrote {}", path.display());
+                    }
+                    Ok(())
                 } else {
                     println!(
                         "To check the build status, run: cargo axiom build status --program-id {program_id}"
@@ -225,7 +585,7 @@ impl BuildCmd {
         }
     }
 
-    fn print_build_status(status: &axiom_sdk::build::BuildStatus) {
+    fn print_build_status(status: &BuildStatus) {
         Formatter::print_section("Build Status");
         Formatter::print_field("ID", &status.id);
         Formatter::print_field("Name", &status.name);
@@ -250,5 +610,51 @@ impl BuildCmd {
             Formatter::print_field("Error", error_message);
         }
         Formatter::print_field("Default Num GPUs", &status.default_num_gpus.to_string());
+
+        if !status.labels.is_empty() {
+            Formatter::print_field("Labels", &status.labels.join(", "));
+        }
+        if status.archived {
+            Formatter::print_field("Archived", "yes");
+        }
+
+        Formatter::print_timeline(&status.into());
+    }
+}
+
+fn wait_for_build(sdk: &AxiomSdk, program_id: &str) -> Result<()> {
+    resume_hint(
+        sdk.wait_for_build_completion(program_id),
+        "build",
+        program_id,
+        &format!("cargo axiom build status --program-id {program_id} --wait"),
+    )
+}
+
+/// Project ID saved in `.axiom/project-id` by a previous build of the program
+pub(crate) fn read_cached_project_id(program_dir: &Path) -> Option<String> {
+    let cache_path = program_dir.join(".axiom").join("project-id");
+    let contents = std::fs::read_to_string(cache_path).ok()?;
+    let trimmed = contents.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Save the project ID so later builds of the program reuse it, unless one is already saved
+pub(crate) fn cache_project_id(program_dir: &Path, project_id: &str) {
+    let cache_dir = program_dir.join(".axiom");
+    let cache_path = cache_dir.join("project-id");
+    if cache_path.exists() {
+        return;
+    }
+    if let Err(e) = std::fs::create_dir_all(&cache_dir) {
+        eprintln!("Warning: failed to create .axiom directory: {e}");
+    } else if let Err(e) = std::fs::write(&cache_path, project_id.as_bytes()) {
+        eprintln!("Warning: failed to write project ID cache: {e}");
+    } else {
+        println!("✓ Saved project ID {project_id} for future builds");
     }
 }
@@ -0,0 +1,113 @@
+use std::path::PathBuf;
+
+use axiom_sdk::bundle::{SignatureStatus, verify_bundle};
+use clap::{Args, Subcommand};
+use eyre::Result;
+
+use crate::formatting::Formatter;
+
+#[derive(Args, Debug)]
+pub struct BundleCmd {
+    #[command(subcommand)]
+    command: BundleSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum BundleSubcommand {
+    /// Check the files of a proof bundle against its manifest and the manifest's
+    /// signature, offline
+    Verify {
+        /// Path to the bundle created by `cargo axiom prove bundle`
+        #[clap(value_name = "FILE")]
+        bundle: PathBuf,
+
+        /// Hex-encoded Ed25519 public key that must have signed the bundle
+        #[clap(long, value_name = "KEY")]
+        public_key: Option<String>,
+    },
+}
+
+impl BundleCmd {
+    pub fn run(self) -> Result<()> {
+        match self.command {
+            BundleSubcommand::Verify { bundle, public_key } => {
+                let verification = verify_bundle(&bundle, public_key.as_deref())?;
+                let manifest = &verification.manifest;
+
+                Formatter::print_section("Proof Bundle");
+                Formatter::print_field("Proof ID", &manifest.proof_id);
+                Formatter::print_field("Program ID", &manifest.program_id);
+                Formatter::print_field("Config ID", &manifest.config_id);
+                Formatter::print_field("App Exe Commit", &manifest.app_exe_commit);
+                Formatter::print_field("App VM Commit", &manifest.app_vm_commit);
+                Formatter::print_field("Bundled At", &manifest.bundled_at);
+                if let Some(cli_version) = &manifest.cli_version {
+                    Formatter::print_field("CLI Version", cli_version);
+                }
+                if let Some(signed_by) = &manifest.signed_by {
+                    Formatter::print_field("Signed By", signed_by);
+                }
+                match &verification.signature {
+                    SignatureStatus::Unsigned => Formatter::print_field("Signature", "none"),
+                    SignatureStatus::Valid { public_key } => {
+                        Formatter::print_field("Signature", "valid");
+                        Formatter::print_field("Public Key", public_key);
+                    }
+                    SignatureStatus::Invalid { reason } => {
+                        Formatter::print_field("Signature", &format!("invalid ({reason})"))
+                    }
+                }
+
+                let mut table = comfy_table::Table::new();
+                table.set_header(["File", "Size", "SHA-256", "Status"]);
+                for file in &manifest.files {
+                    let status = if verification.missing.contains(&file.path) {
+                        "missing"
+                    } else if verification.mismatched.contains(&file.path) {
+                        "modified"
+                    } else {
+                        "ok"
+                    };
+                    table.add_row([
+                        file.path.clone(),
+                        file.size.to_string(),
+                        file.sha256.clone(),
+                        status.to_string(),
+                    ]);
+                }
+                for path in &verification.unlisted {
+                    table.add_row([
+                        path.clone(),
+                        "-".into(),
+                        "-".into(),
+                        "not in manifest".into(),
+                    ]);
+                }
+                println!("\n{table}");
+
+                if !verification.manifest_checksum_ok {
+                    Formatter::print_error(
+                        "The manifest does not match its checksum in manifest.sha256",
+                    );
+                }
+                if let SignatureStatus::Invalid { reason } = &verification.signature {
+                    Formatter::print_error(&format!("Bad manifest signature: {reason}"));
+                }
+                if !verification.is_ok() {
+                    eyre::bail!("Bundle {} failed verification", bundle.display());
+                }
+                Formatter::print_success("All files match the manifest");
+                match (&verification.signature, &public_key) {
+                    (SignatureStatus::Unsigned, _) => Formatter::print_info(
+                        "The bundle is not signed. The checksums detect accidental corruption only, not who made the bundle or whether it was modified",
+                    ),
+                    (SignatureStatus::Valid { .. }, None) => Formatter::print_warning(
+                        "Anyone can sign a bundle with their own key. Check the public key with the signer, or pass it with --public-key",
+                    ),
+                    _ => {}
+                }
+                Ok(())
+            }
+        }
+    }
+}
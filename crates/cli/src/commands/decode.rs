@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+
+use axiom_sdk::{
+    AxiomSdk,
+    public_values::{
+        FieldType, PublicValuesSdk, decode_with_schema, read_proof_public_values, to_hex,
+        u32_words, utf8, words32,
+    },
+};
+use clap::{Args, Subcommand};
+use eyre::Result;
+
+use crate::formatting::Formatter;
+
+#[derive(Args, Debug)]
+pub struct DecodeCmd {
+    #[command(subcommand)]
+    command: DecodeSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum DecodeSubcommand {
+    /// Decode the public values revealed by a program
+    PublicValues {
+        /// Path to an EVM proof file
+        #[clap(long, value_name = "FILE", conflicts_with = "execution_id")]
+        proof_file: Option<PathBuf>,
+
+        /// The execution ID to read the public values from
+        #[clap(long, value_name = "ID", required_unless_present = "proof_file")]
+        execution_id: Option<String>,
+
+        /// Comma-separated types to decode the values as, in order (e.g. "u64,bytes32,address").
+        /// Supported: bool, u8-u128, i8-i128 (little-endian), address, bytes1-bytes32
+        #[clap(long, value_name = "TYPES", value_delimiter = ',')]
+        schema: Vec<FieldType>,
+    },
+}
+
+impl DecodeCmd {
+    pub fn run(self) -> Result<()> {
+        match self.command {
+            DecodeSubcommand::PublicValues {
+                proof_file,
+                execution_id,
+                schema,
+            } => {
+                let bytes = match (proof_file, execution_id) {
+                    (Some(proof_file), _) => read_proof_public_values(&proof_file)?,
+                    (None, Some(execution_id)) => {
+                        let config = axiom_sdk::load_config()?;
+                        let sdk = AxiomSdk::new(config);
+                        sdk.get_execution_public_values(&execution_id)?
+                    }
+                    (None, None) => unreachable!("clap requires a proof file or execution ID"),
+                };
+                print_public_values(&bytes, &schema)
+            }
+        }
+    }
+}
+
+fn print_public_values(bytes: &[u8], schema: &[FieldType]) -> Result<()> {
+    Formatter::print_section("Public Values");
+    Formatter::print_field("Length", &format!("{} bytes", bytes.len()));
+    if bytes.is_empty() {
+        return Ok(());
+    }
+    Formatter::print_field("Raw", &to_hex(bytes));
+
+    if !schema.is_empty() {
+        let (values, left_over) = decode_with_schema(bytes, schema)?;
+        let mut table = comfy_table::Table::new();
+        table.set_header(["#", "Offset", "Type", "Value"]);
+        for (i, value) in values.iter().enumerate() {
+            table.add_row([
+                i.to_string(),
+                value.offset.to_string(),
+                value.field_type.to_string(),
+                value.value.clone(),
+            ]);
+        }
+        println!("\n{table}");
+        if left_over > 0 {
+            Formatter::print_warning(&format!(
+                "{left_over} bytes after the last schema field were not decoded"
+            ));
+        }
+        return Ok(());
+    }
+
+    let mut table = comfy_table::Table::new();
+    table.set_header(["Word", "Offset", "u32 (LE)", "Hex"]);
+    for (i, (word, chunk)) in u32_words(bytes).iter().zip(bytes.chunks(4)).enumerate() {
+        table.add_row([
+            i.to_string(),
+            (i * 4).to_string(),
+            word.to_string(),
+            to_hex(chunk),
+        ]);
+    }
+    println!("\n{table}");
+
+    Formatter::print_section("32-byte Words");
+    for (i, word) in words32(bytes).iter().enumerate() {
+        Formatter::print_field(&format!("[{i}]"), word);
+    }
+
+    if let Some(text) = utf8(bytes)
+        && !text.is_empty()
+    {
+        Formatter::print_section("UTF-8");
+        println!("{text}");
+    }
+    Ok(())
+}
@@ -1,21 +1,31 @@
 pub mod build;
+pub mod bundle;
 pub mod config;
+pub mod decode;
 pub mod init;
+pub mod pipeline;
 pub mod projects;
+pub mod proof;
 pub mod prove;
 pub mod register;
 pub mod run;
 pub mod upload_exe;
 pub mod verify;
 pub mod version;
+pub mod watch;
 
 pub use build::BuildCmd;
+pub use bundle::BundleCmd;
 pub use config::ConfigCmd;
+pub use decode::DecodeCmd;
 pub use init::InitCmd;
+pub use pipeline::PipelineCmd;
 pub use projects::ProjectsCmd;
+pub use proof::ProofCmd;
 pub use prove::ProveCmd;
 pub use register::RegisterCmd;
 pub use run::RunCmd;
 pub use upload_exe::UploadExeCmd;
 pub use verify::VerifyCmd;
 pub use version::VersionCmd;
+pub use watch::WatchCmd;
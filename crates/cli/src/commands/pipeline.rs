@@ -0,0 +1,158 @@
+use axiom_sdk::{
+    AxiomSdk, ProofType,
+    build::{BuildArgs, BuildSdk, ConfigSource, UploadExeArgs},
+    input::Input,
+    pipeline::{PipelineArgs, PipelineReport, PipelineSdk, PipelineUpload},
+};
+use clap::Parser;
+use eyre::Result;
+
+use crate::{
+    commands::{
+        build::{cache_project_id, read_cached_project_id},
+        prove::{validate_num_gpus, validate_priority},
+    },
+    formatting::Formatter,
+    progress::CliProgressCallback,
+};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "pipeline",
+    about = "Build, execute, prove, download and verify a program in one go"
+)]
+pub struct PipelineCmd {
+    /// The configuration ID to use (must reference an already-keyed config)
+    #[clap(long, value_name = "ID")]
+    config_id: Option<String>,
+
+    /// The binary to upload, if the project has multiple binaries
+    #[clap(long, value_name = "BIN")]
+    bin: Option<String>,
+
+    /// The project ID to associate with the program (defaults to the cached project)
+    #[arg(long, value_name = "ID")]
+    project_id: Option<String>,
+
+    /// Upload the source tree and build the program on the Axiom Proving Service
+    /// instead of uploading a locally-built ELF + VMEXE
+    #[clap(long)]
+    remote: bool,
+
+    /// Allow building with uncommitted changes
+    #[clap(long, requires = "remote")]
+    allow_dirty: bool,
+
+    /// Input data for the execution and the proof (file path or hex string)
+    #[clap(long, value_parser, help = "Input to OpenVM program")]
+    input: Option<Input>,
+
+    /// The type of proof to generate (stark or evm)
+    #[clap(long = "type", default_value = "stark")]
+    proof_type: ProofType,
+
+    /// Num GPUs to use for the proof (1-10000)
+    #[clap(long, value_parser = validate_num_gpus)]
+    num_gpus: Option<usize>,
+
+    /// Priority for the proof (1-10, higher = more priority)
+    #[clap(long, value_parser = validate_priority)]
+    priority: Option<u8>,
+}
+
+impl PipelineCmd {
+    pub fn run(self) -> Result<()> {
+        let config = axiom_sdk::load_config()?;
+        let callback = CliProgressCallback::new();
+        let sdk = AxiomSdk::new(config).with_callback(callback);
+
+        let program_dir = std::env::current_dir()?;
+        let project_id = self
+            .project_id
+            .or_else(|| read_cached_project_id(&program_dir));
+        let had_cached_pid = project_id.is_some();
+
+        let upload = if self.remote {
+            PipelineUpload::Remote(BuildArgs {
+                config_source: self.config_id.map(ConfigSource::ConfigId),
+                bin: self.bin,
+                keep_tarball: None,
+                exclude_files: None,
+                include_dirs: None,
+                project_id,
+                project_name: None,
+                allow_dirty: self.allow_dirty,
+                default_num_gpus: None,
+                openvm_rust_toolchain: None,
+                rev: None,
+                allow_secrets: false,
+                vendor_dir: None,
+                refresh_fetch_cache: false,
+            })
+        } else {
+            PipelineUpload::Local(UploadExeArgs {
+                config_id: self.config_id,
+                project_id,
+                project_name: None,
+                bin_name: self.bin,
+                program_name: None,
+                default_num_gpus: None,
+            })
+        };
+
+        let report = sdk.run_pipeline(
+            &program_dir,
+            PipelineArgs {
+                upload,
+                input: self.input,
+                proof_type: self.proof_type,
+                num_gpus: self.num_gpus,
+                priority: self.priority,
+            },
+        );
+
+        if !had_cached_pid
+            && let Some(program_id) = report.steps.first().and_then(|step| step.id.as_deref())
+            && let Ok(status) = sdk.get_build_status(program_id)
+        {
+            cache_project_id(&program_dir, &status.project_id);
+        }
+
+        Self::print_report(&report);
+        match report.failed_step() {
+            Some(step) => eyre::bail!(
+                "Pipeline failed at the {} step: {}",
+                step.stage,
+                step.error.as_deref().unwrap_or_default()
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn print_report(report: &PipelineReport) {
+        let mut table = comfy_table::Table::new();
+        table.set_header(["Step", "ID", "Duration", "Result"]);
+        for step in &report.steps {
+            table.add_row([
+                step.stage.to_string(),
+                step.id.clone().unwrap_or_else(|| "-".to_string()),
+                format!("{:.1}s", step.elapsed.as_secs_f64()),
+                if step.error.is_some() {
+                    "✗ failed".to_string()
+                } else {
+                    "✓ ok".to_string()
+                },
+            ]);
+        }
+        println!("\n{table}");
+
+        Formatter::print_section("Pipeline Summary");
+        Formatter::print_field(
+            "Total Duration",
+            &format!("{:.1}s", report.total_elapsed().as_secs_f64()),
+        );
+        if let Some(proof_path) = &report.proof_path {
+            Formatter::print_field("Proof", &proof_path.display().to_string());
+        }
+    }
+}
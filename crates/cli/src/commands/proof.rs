@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use axiom_sdk::{calldata::EvmProof, public_values::to_hex};
+use clap::{Args, Subcommand};
+use eyre::{Context, Result};
+
+use crate::formatting::Formatter;
+
+#[derive(Args, Debug)]
+pub struct ProofCmd {
+    #[command(subcommand)]
+    command: ProofSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum ProofSubcommand {
+    /// Encode an EVM proof as calldata for the on-chain verifier's `verify` call
+    Calldata {
+        /// Path to the EVM proof file
+        #[clap(value_name = "FILE")]
+        proof: PathBuf,
+
+        /// Address of the verifier contract to put in the `cast call` command
+        #[clap(long, value_name = "ADDRESS")]
+        verifier: Option<String>,
+
+        /// RPC URL to put in the `cast call` command
+        #[clap(long, value_name = "URL")]
+        rpc_url: Option<String>,
+
+        /// Directory to write the calldata, command and fixture to (defaults to the
+        /// proof's directory)
+        #[clap(long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
+    },
+}
+
+impl ProofCmd {
+    pub fn run(self) -> Result<()> {
+        match self.command {
+            ProofSubcommand::Calldata {
+                proof: proof_path,
+                verifier,
+                rpc_url,
+                output_dir,
+            } => {
+                let proof = EvmProof::read(&proof_path)?;
+                let output_dir = output_dir.unwrap_or_else(|| {
+                    proof_path
+                        .parent()
+                        .map(|dir| dir.to_path_buf())
+                        .unwrap_or_default()
+                });
+                std::fs::create_dir_all(&output_dir).with_context(|| {
+                    format!("Failed to create directory {}", output_dir.display())
+                })?;
+                let stem = proof_path
+                    .file_stem()
+                    .map_or_else(|| "proof".into(), |stem| stem.to_string_lossy());
+
+                let calldata = proof.verifier_calldata();
+                let command = proof.cast_call_command(verifier.as_deref(), rpc_url.as_deref());
+                let calldata_path = output_dir.join(format!("{stem}.calldata"));
+                let command_path = output_dir.join(format!("{stem}.cast.sh"));
+                let fixture_path = output_dir.join(format!("{stem}.fixture.json"));
+                std::fs::write(&calldata_path, to_hex(&calldata))?;
+                std::fs::write(&command_path, format!("#!/bin/sh\n{command}\n"))?;
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    std::fs::set_permissions(
+                        &command_path,
+                        std::fs::Permissions::from_mode(0o755),
+                    )?;
+                }
+                std::fs::write(
+                    &fixture_path,
+                    serde_json::to_string_pretty(&proof.fixture())?,
+                )?;
+
+                Formatter::print_section("Verifier Calldata");
+                Formatter::print_field("OpenVM Version", &proof.version);
+                Formatter::print_field("Public Values", &to_hex(&proof.user_public_values));
+                Formatter::print_field("App Exe Commit", &to_hex(&proof.app_exe_commit));
+                Formatter::print_field("App VM Commit", &to_hex(&proof.app_vm_commit));
+                Formatter::print_field("Size", &format!("{} bytes", calldata.len()));
+                Formatter::print_field("Calldata", &calldata_path.display().to_string());
+                Formatter::print_field("Cast Command", &command_path.display().to_string());
+                Formatter::print_field("Fixture", &fixture_path.display().to_string());
+                if verifier.is_none() || rpc_url.is_none() {
+                    println!(
+                        "\nSet VERIFIER_ADDRESS and RPC_URL, or pass --verifier and --rpc-url, before running {}",
+                        command_path.display()
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+}
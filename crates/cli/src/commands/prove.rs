@@ -1,13 +1,94 @@
-use std::path::PathBuf;
-
-use axiom_sdk::{AxiomSdk, ProofType, input::Input, prove::ProveSdk};
+use std::{collections::HashSet, path::PathBuf};
+
+use axiom_sdk::{
+    AxiomSdk, ProofType,
+    analyze::{AnalyzeSdk, ProofAnalysis},
+    batch::{BatchProveArgs, BatchSdk, BatchSummary, UNSAVED_STATE},
+    bundle::{BundleArgs, BundleSdk, BundleSigner},
+    estimate::{EstimateArgs, EstimateSdk, ProofEstimate, proof_duration},
+    format_duration,
+    input::Input,
+    prove::{ProofListQuery, ProofStatus, ProofSubmission, ProveSdk, RetryArgs},
+    timeline::{Timeline, TimelineSdk},
+};
 use clap::{Args, Subcommand};
-use comfy_table;
-use eyre::Result;
-
-use crate::{formatting::Formatter, progress::CliProgressCallback};
+use eyre::{OptionExt, Result};
+use indicatif::HumanBytes;
+
+use crate::{
+    formatting::Formatter,
+    hooks::HookArgs,
+    interrupt::{confirm, handle_proof_interrupt, is_interrupted, resume_hint},
+    listing::{
+        Column, ListFilterArgs, build_table, duration, format_optional_duration, or_dash,
+        parse_date_filter, select_columns,
+    },
+    logs::FollowLogsArgs,
+    progress::CliProgressCallback,
+};
+
+const PROOF_COLUMNS: [Column<ProofStatus>; 11] = [
+    Column {
+        name: "id",
+        header: "ID",
+        value: |p| or_dash(&p.id),
+    },
+    Column {
+        name: "state",
+        header: "State",
+        value: |p| or_dash(&p.state),
+    },
+    Column {
+        name: "proof_type",
+        header: "Proof type",
+        value: |p| or_dash(&p.proof_type),
+    },
+    Column {
+        name: "program",
+        header: "Program ID",
+        value: |p| or_dash(&p.program_uuid),
+    },
+    Column {
+        name: "created_by",
+        header: "Created By",
+        value: |p| or_dash(&p.created_by),
+    },
+    Column {
+        name: "created_at",
+        header: "Created At",
+        value: |p| or_dash(&p.created_at),
+    },
+    Column {
+        name: "cells_used",
+        header: "Cells Used",
+        value: |p| p.cells_used.to_string(),
+    },
+    Column {
+        name: "num_instructions",
+        header: "Instructions",
+        value: |p| {
+            p.num_instructions
+                .map_or("-".to_string(), |n| n.to_string())
+        },
+    },
+    Column {
+        name: "num_gpus",
+        header: "Num GPUs",
+        value: |p| p.num_gpus.to_string(),
+    },
+    Column {
+        name: "priority",
+        header: "Priority",
+        value: |p| p.priority.to_string(),
+    },
+    Column {
+        name: "duration",
+        header: "Duration",
+        value: |p| duration(Timeline::from(p)),
+    },
+];
 
-fn validate_priority(s: &str) -> Result<u8, String> {
+pub(crate) fn validate_priority(s: &str) -> Result<u8, String> {
     let priority: u8 = s.parse().map_err(|_| "Priority must be a number")?;
     if (1..=10).contains(&priority) {
         Ok(priority)
@@ -16,7 +97,7 @@ fn validate_priority(s: &str) -> Result<u8, String> {
     }
 }
 
-fn validate_num_gpus(s: &str) -> Result<usize, String> {
+pub(crate) fn validate_num_gpus(s: &str) -> Result<usize, String> {
     let num_gpus: usize = s.parse().map_err(|_| "Number of GPUs must be a number")?;
     if (1..=10000).contains(&num_gpus) {
         Ok(num_gpus)
@@ -49,12 +130,73 @@ enum ProveSubcommand {
         /// Don't save the proof artifact on completion
         #[clap(long)]
         no_save: bool,
+
+        /// Cancel the proof without asking if waiting is interrupted with Ctrl-C
+        #[clap(long, requires = "wait")]
+        cancel_on_interrupt: bool,
+
+        #[command(flatten)]
+        hooks: HookArgs,
     },
     /// Download logs for a proof
     Logs {
         /// The proof ID to download logs for
         #[clap(long, value_name = "ID")]
         proof_id: String,
+
+        #[command(flatten)]
+        follow_args: FollowLogsArgs,
+    },
+    /// Submit a proof again with the same input and settings
+    Retry {
+        /// The proof ID to retry
+        #[clap(long, value_name = "ID")]
+        proof_id: String,
+
+        /// Use this number of GPUs instead of the original one
+        #[clap(long, value_parser = validate_num_gpus)]
+        num_gpus: Option<usize>,
+
+        /// Use this priority instead of the original one
+        #[clap(long, value_parser = validate_priority)]
+        priority: Option<u8>,
+
+        /// Don't wait for the new proof to complete
+        #[clap(long)]
+        detach: bool,
+
+        /// Cancel the new proof without asking if waiting is interrupted with Ctrl-C
+        #[clap(long, conflicts_with = "detach")]
+        cancel_on_interrupt: bool,
+
+        #[command(flatten)]
+        hooks: HookArgs,
+    },
+    /// Break a proof's logs down into phases and timings, and explain why it failed
+    Analyze {
+        /// The proof ID to analyze
+        #[clap(long, value_name = "ID")]
+        proof_id: String,
+    },
+    /// Collect a proof and its logs, statuses, config and input into one archive with a
+    /// manifest of SHA-256 hashes. Check it later with `cargo axiom bundle verify`
+    Bundle {
+        /// The proof ID to bundle
+        #[clap(long, value_name = "ID")]
+        proof_id: String,
+
+        /// Path of the archive (defaults to the proof's artifacts directory)
+        #[clap(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Ed25519 private key (PKCS#8 PEM, e.g. from `openssl genpkey -algorithm ed25519`)
+        /// to sign the manifest with
+        #[clap(long, value_name = "FILE")]
+        signing_key: Option<PathBuf>,
+
+        /// Name of the signer to record in the manifest
+        #[clap(long, value_name = "NAME", requires = "signing_key")]
+        signer: Option<String>,
     },
     /// Download proof artifacts
     Download {
@@ -73,9 +215,13 @@ enum ProveSubcommand {
 
     /// List all proofs for a program
     List {
-        /// The ID of the program to list proofs for
+        /// The ID of the program to list proofs for (all programs if omitted)
         #[arg(long, value_name = "ID")]
-        program_id: String,
+        program_id: Option<String>,
+
+        /// Only list proofs of programs in this project
+        #[arg(long, value_name = "ID")]
+        project: Option<String>,
 
         /// Page number (default: 1)
         #[arg(long, default_value = "1")]
@@ -84,12 +230,98 @@ enum ProveSubcommand {
         /// Page size (default: 20)
         #[arg(long, default_value = "20")]
         page_size: u32,
+
+        #[command(flatten)]
+        filters: ListFilterArgs,
+
+        /// Only show proofs of this type
+        #[arg(long, value_name = "TYPE", value_parser = ["stark", "evm"])]
+        proof_type: Option<String>,
+    },
+    /// Show queue and proving time percentiles of a program's proofs by GPUs and priority
+    Stats {
+        /// The ID of the program whose proofs to summarize
+        #[arg(long, value_name = "ID")]
+        program_id: String,
+
+        /// Only include proofs of this type
+        #[arg(long, value_name = "TYPE", value_parser = ["stark", "evm"])]
+        proof_type: Option<String>,
+
+        /// Number of most recent successful proofs to include
+        #[arg(long, value_name = "N", default_value = "200")]
+        limit: u32,
     },
-    /// Cancel a running proof
+    /// Cancel a running proof, or all queued and running proofs matching the filters
     Cancel {
         /// The proof ID to cancel
-        #[clap(long, value_name = "ID")]
-        proof_id: String,
+        #[clap(
+            long,
+            value_name = "ID",
+            required_unless_present_any = ["program_id", "all_mine"],
+            conflicts_with_all = ["program_id", "all_mine", "created_by", "state", "created_after"]
+        )]
+        proof_id: Option<String>,
+
+        /// Cancel the matching proofs of this program
+        #[clap(long, value_name = "ID", conflicts_with = "all_mine")]
+        program_id: Option<String>,
+
+        /// Cancel the matching proofs of all programs in your account
+        #[clap(long)]
+        all_mine: bool,
+
+        /// Only cancel proofs created by this user
+        #[clap(long, value_name = "USER")]
+        created_by: Option<String>,
+
+        /// Comma-separated states of the proofs to cancel
+        #[clap(
+            long,
+            value_name = "STATES",
+            value_delimiter = ',',
+            default_value = "Queued,InProgress",
+            value_parser = ["Queued", "InProgress"]
+        )]
+        state: Vec<String>,
+
+        /// Only cancel proofs created after this date (YYYY-MM-DD or RFC3339)
+        #[clap(long, value_name = "DATE", value_parser = parse_date_filter)]
+        created_after: Option<String>,
+
+        /// Cancel without asking for confirmation
+        #[clap(long, short = 'y')]
+        yes: bool,
+    },
+    /// Prove a program over many inputs, resuming where a previous run left off
+    Batch {
+        /// The ID of the program to generate proofs for
+        #[arg(long, value_name = "ID")]
+        program_id: String,
+
+        /// Directory of JSON input files, or a JSONL file with one input per line
+        #[arg(long, value_name = "PATH")]
+        inputs: PathBuf,
+
+        /// The type of proof to generate (stark or evm)
+        #[clap(long = "type", default_value = "stark")]
+        proof_type: ProofType,
+
+        /// Num GPUs to use for each proof (1-10000)
+        #[clap(long, value_parser = validate_num_gpus)]
+        num_gpus: Option<usize>,
+
+        /// Priority for the proofs (1-10, higher = more priority)
+        #[clap(long, value_parser = validate_priority)]
+        priority: Option<u8>,
+
+        /// Maximum number of proofs running at the same time
+        #[arg(long, value_name = "N", default_value = "4")]
+        concurrency: usize,
+
+        /// Directory for the proofs and the batch state file
+        #[arg(long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
     },
 }
 
@@ -126,6 +358,17 @@ pub struct ProveArgs {
     /// served by proof download.
     #[clap(long = "deferred-proof", value_name = "PATH")]
     deferred_proofs: Vec<std::path::PathBuf>,
+
+    /// Cancel the proof without asking if waiting is interrupted with Ctrl-C
+    #[clap(long, conflicts_with = "detach")]
+    cancel_on_interrupt: bool,
+
+    /// Execute the program and estimate the proof's cost and duration instead of generating it
+    #[clap(long, conflicts_with_all = ["detach", "deferred_proofs"])]
+    estimate: bool,
+
+    #[command(flatten)]
+    hooks: HookArgs,
 }
 
 impl ProveCmd {
@@ -139,9 +382,17 @@ impl ProveCmd {
                 proof_id,
                 wait,
                 no_save,
+                cancel_on_interrupt,
+                hooks,
             }) => {
                 if wait {
-                    sdk.wait_for_proof_completion(&proof_id, !no_save)?;
+                    let sdk = sdk.with_completion_hooks(hooks.into());
+                    handle_proof_interrupt(
+                        &sdk,
+                        sdk.wait_for_proof_completion(&proof_id, !no_save),
+                        &proof_id,
+                        cancel_on_interrupt,
+                    )?;
                 } else {
                     let proof_status = sdk.get_proof_status(&proof_id)?;
                     Self::print_proof_status(&proof_status);
@@ -173,42 +424,55 @@ impl ProveCmd {
                 sdk.get_generated_proof(&proof_id, &proof_type, output_path)?;
                 Ok(())
             }
-            Some(ProveSubcommand::Logs { proof_id }) => sdk.get_proof_logs(&proof_id),
+            Some(ProveSubcommand::Logs {
+                proof_id,
+                follow_args,
+            }) => {
+                if follow_args.follow {
+                    resume_hint(
+                        sdk.follow_proof_logs(&proof_id, &follow_args.filter(), follow_args.save),
+                        "proof",
+                        &proof_id,
+                        &format!("cargo axiom prove logs --proof-id {proof_id} --follow"),
+                    )
+                } else {
+                    sdk.get_proof_logs(&proof_id)
+                }
+            }
             Some(ProveSubcommand::List {
                 program_id,
+                project,
                 page,
                 page_size,
+                filters,
+                proof_type,
             }) => {
-                let response = sdk.list_proofs(&program_id, Some(page), Some(page_size))?;
+                // Show which program each item belongs to when listing across programs
+                let default_columns: &[&str] = if program_id.is_some() {
+                    &["id", "state", "proof_type", "created_at"]
+                } else {
+                    &["id", "state", "proof_type", "created_at", "program"]
+                };
+                let columns =
+                    select_columns(filters.columns.as_deref(), &PROOF_COLUMNS, default_columns)?;
+                let query = ProofListQuery {
+                    program_id,
+                    project_id: project,
+                    state: filters.status,
+                    proof_type,
+                    created_by: filters.created_by,
+                    created_after: filters.created_after,
+                    created_before: filters.created_before,
+                    sort: filters.sort,
+                };
+                let response = sdk.search_proofs(Some(page), Some(page_size), &query)?;
 
                 if response.items.is_empty() {
                     println!("No proofs found");
                     return Ok(());
                 }
 
-                // Create a new table
-                let mut table = comfy_table::Table::new();
-                table.set_header(["ID", "State", "Proof type", "Created At"]);
-
-                // Add rows to the table
-                for proof_status in response.items {
-                    let get_value = |s: &str| {
-                        if s.is_empty() {
-                            "-".to_string()
-                        } else {
-                            s.to_string()
-                        }
-                    };
-                    let id = get_value(&proof_status.id);
-                    let status = get_value(&proof_status.state);
-                    let proof_type = get_value(&proof_status.proof_type);
-                    let created_at = get_value(&proof_status.created_at);
-
-                    table.add_row([id, status, proof_type, created_at]);
-                }
-
-                // Print the table
-                println!("{table}");
+                println!("{}", build_table(&columns, &response.items));
 
                 let pagination = &response.pagination;
                 println!(
@@ -218,7 +482,151 @@ impl ProveCmd {
 
                 Ok(())
             }
-            Some(ProveSubcommand::Cancel { proof_id }) => {
+            Some(ProveSubcommand::Retry {
+                proof_id,
+                num_gpus,
+                priority,
+                detach,
+                cancel_on_interrupt,
+                hooks,
+            }) => {
+                let sdk = sdk.with_completion_hooks(hooks.into());
+                let new_proof_id = sdk.retry_proof(&proof_id, RetryArgs { num_gpus, priority })?;
+                if detach {
+                    println!(
+                        "To check the proof status, run: cargo axiom prove status --proof-id {new_proof_id}"
+                    );
+                } else {
+                    handle_proof_interrupt(
+                        &sdk,
+                        sdk.wait_for_proof_completion(&new_proof_id, true),
+                        &new_proof_id,
+                        cancel_on_interrupt,
+                    )?;
+                }
+                Ok(())
+            }
+            Some(ProveSubcommand::Analyze { proof_id }) => {
+                let analysis = sdk.analyze_proof(&proof_id)?;
+                Self::print_analysis(&analysis);
+                Ok(())
+            }
+            Some(ProveSubcommand::Bundle {
+                proof_id,
+                output,
+                signing_key,
+                signer,
+            }) => {
+                let signer = signing_key
+                    .map(|path| BundleSigner::from_pem_file(&path, signer))
+                    .transpose()?;
+                let bundle_path = sdk.bundle_proof(&proof_id, BundleArgs { output, signer })?;
+                println!(
+                    "To check the bundle, run: cargo axiom bundle verify {}",
+                    bundle_path.display()
+                );
+                Ok(())
+            }
+            Some(ProveSubcommand::Stats {
+                program_id,
+                proof_type,
+                limit,
+            }) => {
+                let stats = sdk.proof_time_stats(&program_id, proof_type, limit)?;
+                if stats.is_empty() {
+                    Formatter::print_info("No successful proofs found");
+                    return Ok(());
+                }
+                let mut table = comfy_table::Table::new();
+                table.set_header([
+                    "Num GPUs",
+                    "Priority",
+                    "Proofs",
+                    "Queue p50",
+                    "Queue p95",
+                    "Proving p50",
+                    "Proving p95",
+                ]);
+                for group in &stats {
+                    table.add_row([
+                        group.num_gpus.to_string(),
+                        group.priority.to_string(),
+                        group.count.to_string(),
+                        format_optional_duration(group.queue_p50),
+                        format_optional_duration(group.queue_p95),
+                        format_optional_duration(group.run_p50),
+                        format_optional_duration(group.run_p95),
+                    ]);
+                }
+                println!("{table}");
+                Ok(())
+            }
+            Some(ProveSubcommand::Cancel {
+                proof_id: None,
+                program_id,
+                created_by,
+                state,
+                created_after,
+                yes,
+                ..
+            }) => {
+                let mut proofs = Vec::new();
+                let mut seen = HashSet::new();
+                for state in state {
+                    let query = ProofListQuery {
+                        program_id: program_id.clone(),
+                        created_by: created_by.clone(),
+                        state: Some(state),
+                        created_after: created_after.clone(),
+                        sort: Some("created_at".to_string()),
+                        ..Default::default()
+                    };
+                    // A proof that changes state between queries is returned twice
+                    proofs.extend(
+                        sdk.search_all_proofs(&query)?
+                            .into_iter()
+                            .filter(|proof| seen.insert(proof.id.clone())),
+                    );
+                }
+                if proofs.is_empty() {
+                    Formatter::print_info("No proofs to cancel");
+                    return Ok(());
+                }
+
+                let mut table = comfy_table::Table::new();
+                table.set_header(["ID", "Program", "State", "Type", "Created At"]);
+                for proof in &proofs {
+                    table.add_row([
+                        &proof.id,
+                        &proof.program_uuid,
+                        &proof.state,
+                        &proof.proof_type,
+                        &proof.created_at,
+                    ]);
+                }
+                println!("{table}");
+
+                let question = format!("Cancel these {} proofs?", proofs.len());
+                if !yes && !confirm(&question) {
+                    eyre::bail!("Nothing was canceled. Pass --yes to cancel without confirmation");
+                }
+                let proof_ids: Vec<String> = proofs.into_iter().map(|proof| proof.id).collect();
+                let results = sdk.cancel_proofs(&proof_ids);
+                let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+                if failed > 0 {
+                    eyre::bail!(
+                        "{} of {} proofs could not be canceled",
+                        failed,
+                        results.len()
+                    );
+                }
+                Formatter::print_success(&format!("Canceled {} proofs", results.len()));
+                Ok(())
+            }
+            Some(ProveSubcommand::Cancel {
+                proof_id: Some(proof_id),
+                ..
+            }) => {
                 let message = sdk.cancel_proof(&proof_id)?;
                 println!("✓ {}", message);
 
@@ -226,10 +634,68 @@ impl ProveCmd {
                 sdk.wait_for_proof_cancellation(&proof_id)?;
                 Ok(())
             }
+            Some(ProveSubcommand::Batch {
+                program_id,
+                inputs,
+                proof_type,
+                num_gpus,
+                priority,
+                concurrency,
+                output_dir,
+            }) => {
+                let result = sdk.prove_batch(BatchProveArgs {
+                    program_id,
+                    inputs,
+                    proof_type,
+                    num_gpus,
+                    priority,
+                    concurrency,
+                    output_dir,
+                });
+                if let Err(e) = &result
+                    && is_interrupted(e)
+                {
+                    println!();
+                    Formatter::print_warning(
+                        "Stopped waiting for the batch. Submitted proofs keep running on the Axiom Proving Service.",
+                    );
+                    Formatter::print_info("To resume, run the same command again");
+                }
+                let summary = result?;
+                Self::print_batch_summary(&summary);
+
+                let failed = summary.count("Failed") + summary.count("Canceled");
+                if failed > 0 {
+                    eyre::bail!("{failed} proofs in the batch did not succeed");
+                }
+                let unsaved = summary.count(UNSAVED_STATE);
+                if unsaved > 0 {
+                    eyre::bail!(
+                        "{unsaved} proofs succeeded but could not be saved. Run the same command again to download them"
+                    );
+                }
+                Ok(())
+            }
+            None if self.prove_args.estimate => {
+                let program_id = self
+                    .prove_args
+                    .program_id
+                    .ok_or_eyre("Program ID is required. Use --program-id to specify.")?;
+                let estimate = sdk.estimate_proof(EstimateArgs {
+                    program_id,
+                    input: self.prove_args.input,
+                    proof_type: self.prove_args.proof_type,
+                    num_gpus: self.prove_args.num_gpus,
+                })?;
+                Self::print_estimate(&estimate);
+                Ok(())
+            }
             None => {
                 use crate::progress::CliProgressCallback;
                 let callback = CliProgressCallback::new();
-                let sdk = sdk.with_callback(callback);
+                let sdk = sdk
+                    .with_callback(callback)
+                    .with_completion_hooks(self.prove_args.hooks.into());
                 let args = axiom_sdk::prove::ProveArgs {
                     program_id: self.prove_args.program_id,
                     input: self.prove_args.input,
@@ -241,7 +707,12 @@ impl ProveCmd {
                 let proof_id = sdk.generate_new_proof(args)?;
 
                 if !self.prove_args.detach {
-                    sdk.wait_for_proof_completion(&proof_id, true)?;
+                    handle_proof_interrupt(
+                        &sdk,
+                        sdk.wait_for_proof_completion(&proof_id, true),
+                        &proof_id,
+                        self.prove_args.cancel_on_interrupt,
+                    )?;
                 } else {
                     println!(
                         "To check the proof status, run: cargo axiom prove status --proof-id {proof_id}"
@@ -252,6 +723,158 @@ impl ProveCmd {
         }
     }
 
+    fn print_estimate(estimate: &ProofEstimate) {
+        let value_or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+
+        Formatter::print_section("Proof Estimate");
+        Formatter::print_field("Program ID", &estimate.program_id);
+        Formatter::print_field("Proof Type", &estimate.proof_type.to_string());
+        Formatter::print_field(
+            "Total Cycles",
+            &value_or_dash(estimate.total_cycles.map(|n| n.to_string())),
+        );
+        Formatter::print_field(
+            "Segments",
+            &value_or_dash(estimate.num_segments.map(|n| n.to_string())),
+        );
+        Formatter::print_field("Cost", &value_or_dash(estimate.cost.map(|n| n.to_string())));
+        Formatter::print_field("Num GPUs", &estimate.num_gpus.to_string());
+        Formatter::print_field(
+            "Estimated Duration",
+            &format_optional_duration(estimate.estimated_duration),
+        );
+
+        if estimate.past_proofs.is_empty() {
+            println!();
+            Formatter::print_info("No past proofs of this type to compare with");
+            return;
+        }
+
+        Formatter::print_section("Recent Proofs");
+        Formatter::print_field(
+            "Average Cells Used",
+            &value_or_dash(estimate.average_cells_used().map(|n| n.to_string())),
+        );
+        let mut table = comfy_table::Table::new();
+        table.set_header([
+            "ID",
+            "Num GPUs",
+            "Priority",
+            "Cycles",
+            "Cells Used",
+            "Duration",
+        ]);
+        for proof in &estimate.past_proofs {
+            table.add_row([
+                proof.id.clone(),
+                proof.num_gpus.to_string(),
+                proof.priority.to_string(),
+                value_or_dash(proof.num_instructions.map(|n| n.to_string())),
+                proof.cells_used.to_string(),
+                format_optional_duration(proof_duration(proof)),
+            ]);
+        }
+        println!("{table}");
+    }
+
+    fn print_analysis(analysis: &ProofAnalysis) {
+        let proof = &analysis.proof;
+
+        Formatter::print_section("Proof Analysis");
+        Formatter::print_field("ID", &proof.id);
+        Formatter::print_field("State", &proof.state);
+        Formatter::print_field("Num GPUs", &proof.num_gpus.to_string());
+        Formatter::print_field("Queue Time", &format_optional_duration(analysis.queue_time));
+        Formatter::print_field("Run Time", &format_optional_duration(analysis.run_time));
+        Formatter::print_field(
+            "Execution Time",
+            &format_optional_duration(analysis.logs.execution_time()),
+        );
+        Formatter::print_field(
+            "Proving Time",
+            &format_optional_duration(analysis.logs.proving_time()),
+        );
+        if let Some(peak_memory) = analysis.logs.peak_memory {
+            Formatter::print_field("Peak Memory", &HumanBytes(peak_memory).to_string());
+        }
+        Formatter::print_field("Logs", &analysis.logs_path.display().to_string());
+
+        if !analysis.logs.phases.is_empty() {
+            Formatter::print_section("Phases");
+            let mut table = comfy_table::Table::new();
+            table.set_header(["Phase", "Started At", "Duration"]);
+            for span in &analysis.logs.phases {
+                table.add_row([
+                    span.item.to_string(),
+                    span.started_at.to_rfc3339(),
+                    format_duration(span.duration().as_secs() as i64),
+                ]);
+            }
+            println!("{table}");
+        }
+
+        if let Some(slowest) = analysis.logs.segments.iter().max_by_key(|s| s.duration()) {
+            let segments = &analysis.logs.segments;
+            let total: std::time::Duration = segments.iter().map(|s| s.duration()).sum();
+            Formatter::print_section("Segments");
+            Formatter::print_field("Count", &segments.len().to_string());
+            Formatter::print_field(
+                "Average",
+                &format_duration((total / segments.len() as u32).as_secs() as i64),
+            );
+            Formatter::print_field(
+                "Slowest",
+                &format!(
+                    "#{} ({})",
+                    slowest.item,
+                    format_duration(slowest.duration().as_secs() as i64)
+                ),
+            );
+        }
+
+        if proof.state == "Failed" {
+            Formatter::print_section("Failure");
+            if let Some(error_message) = &proof.error_message {
+                Formatter::print_field("Error", error_message);
+            }
+            match analysis.failure {
+                Some(cause) => {
+                    Formatter::print_field("Likely Cause", cause.description());
+                    Formatter::print_field("Hint", cause.hint());
+                }
+                None => Formatter::print_info("The failure does not match any known cause"),
+            }
+        }
+    }
+
+    fn print_batch_summary(summary: &BatchSummary) {
+        let mut table = comfy_table::Table::new();
+        table.set_header(["Input", "Proof ID", "State", "Duration", "Cells Used"]);
+        for (name, entry) in &summary.state.entries {
+            table.add_row([
+                name.clone(),
+                entry.proof_id.clone().unwrap_or_else(|| "-".to_string()),
+                entry.state.clone(),
+                entry.duration.clone().unwrap_or_else(|| "-".to_string()),
+                entry
+                    .cells_used
+                    .map_or("-".to_string(), |cells| cells.to_string()),
+            ]);
+        }
+        println!("\n{table}");
+
+        Formatter::print_section("Batch Summary");
+        Formatter::print_field("Succeeded", &summary.count("Succeeded").to_string());
+        Formatter::print_field(
+            "Succeeded, Not Saved",
+            &summary.count(UNSAVED_STATE).to_string(),
+        );
+        Formatter::print_field("Failed", &summary.count("Failed").to_string());
+        Formatter::print_field("Canceled", &summary.count("Canceled").to_string());
+        Formatter::print_field("Total Cells Used", &summary.total_cells_used().to_string());
+        Formatter::print_field("Results", &summary.output_dir.display().to_string());
+    }
+
     fn print_proof_status(status: &axiom_sdk::prove::ProofStatus) {
         Formatter::print_section("Proof Status");
         Formatter::print_field("ID", &status.id);
@@ -273,6 +896,18 @@ impl ProveCmd {
             Formatter::print_field("Error", error_message);
         }
 
+        // Retries are only known for proofs submitted from this directory
+        if let Ok(submission) = ProofSubmission::load(&status.program_uuid, &status.id) {
+            if let Some(retry_of) = &submission.retry_of {
+                Formatter::print_field("Retry Of", retry_of);
+            }
+            if !submission.retries.is_empty() {
+                Formatter::print_field("Retried As", &submission.retries.join(", "));
+            }
+        }
+
+        Formatter::print_timeline(&status.into());
+
         Formatter::print_section("Configuration");
         Formatter::print_field("Num GPUs", &status.num_gpus.to_string());
         Formatter::print_field("Priority", &status.priority.to_string());
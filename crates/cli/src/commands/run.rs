@@ -1,9 +1,73 @@
-use axiom_sdk::{AxiomSdk, input::Input, run::RunSdk};
+use axiom_sdk::{
+    AxiomSdk,
+    input::Input,
+    run::{ExecutionListQuery, ExecutionStatus, RunSdk},
+    timeline::Timeline,
+};
 use clap::{Args, Subcommand};
-use comfy_table;
 use eyre::Result;
 
-use crate::{formatting::Formatter, progress::CliProgressCallback};
+use crate::{
+    formatting::Formatter,
+    hooks::HookArgs,
+    interrupt::resume_hint,
+    listing::{Column, ListFilterArgs, build_table, duration, or_dash, select_columns},
+    logs::FollowLogsArgs,
+    progress::CliProgressCallback,
+};
+
+const EXECUTION_COLUMNS: [Column<ExecutionStatus>; 10] = [
+    Column {
+        name: "id",
+        header: "ID",
+        value: |e| or_dash(&e.id),
+    },
+    Column {
+        name: "status",
+        header: "Status",
+        value: |e| or_dash(&e.status),
+    },
+    Column {
+        name: "mode",
+        header: "Mode",
+        value: |e| or_dash(&e.mode),
+    },
+    Column {
+        name: "program",
+        header: "Program ID",
+        value: |e| or_dash(&e.program_uuid),
+    },
+    Column {
+        name: "created_by",
+        header: "Created By",
+        value: |e| or_dash(&e.created_by),
+    },
+    Column {
+        name: "created_at",
+        header: "Created At",
+        value: |e| or_dash(&e.created_at),
+    },
+    Column {
+        name: "total_cycle",
+        header: "Total Cycles",
+        value: |e| e.total_cycle.map_or("-".to_string(), |n| n.to_string()),
+    },
+    Column {
+        name: "num_segments",
+        header: "Segments",
+        value: |e| e.num_segments.map_or("-".to_string(), |n| n.to_string()),
+    },
+    Column {
+        name: "cost",
+        header: "Cost",
+        value: |e| e.cost.map_or("-".to_string(), |n| n.to_string()),
+    },
+    Column {
+        name: "duration",
+        header: "Duration",
+        value: |e| duration(Timeline::from(e)),
+    },
+];
 
 #[derive(Args, Debug)]
 pub struct RunCmd {
@@ -25,13 +89,20 @@ enum RunSubcommand {
         /// Wait for the execution to complete
         #[clap(long)]
         wait: bool,
+
+        #[command(flatten)]
+        hooks: HookArgs,
     },
 
     /// List all executions for a program
     List {
-        /// The ID of the program to list executions for
+        /// The ID of the program to list executions for (all programs if omitted)
         #[arg(long, value_name = "ID")]
-        program_id: String,
+        program_id: Option<String>,
+
+        /// Only list executions of programs in this project
+        #[arg(long, value_name = "ID")]
+        project: Option<String>,
 
         /// Page number (default: 1)
         #[arg(long, default_value = "1")]
@@ -40,6 +111,13 @@ enum RunSubcommand {
         /// Page size (default: 20)
         #[arg(long, default_value = "20")]
         page_size: u32,
+
+        #[command(flatten)]
+        filters: ListFilterArgs,
+
+        /// Only show executions in this mode
+        #[arg(long, value_name = "MODE", value_parser = ["pure", "meter", "segment"])]
+        mode: Option<String>,
     },
 
     /// Download logs for an execution
@@ -47,6 +125,9 @@ enum RunSubcommand {
         /// The execution ID to download logs for
         #[clap(long, value_name = "ID")]
         execution_id: String,
+
+        #[command(flatten)]
+        follow_args: FollowLogsArgs,
     },
 }
 
@@ -67,6 +148,9 @@ pub struct RunArgs {
     /// Run in detached mode (don't wait for completion)
     #[clap(long)]
     detach: bool,
+
+    #[command(flatten)]
+    hooks: HookArgs,
 }
 
 impl RunCmd {
@@ -76,9 +160,19 @@ impl RunCmd {
         let sdk = AxiomSdk::new(config).with_callback(callback);
 
         match self.command {
-            Some(RunSubcommand::Status { execution_id, wait }) => {
+            Some(RunSubcommand::Status {
+                execution_id,
+                wait,
+                hooks,
+            }) => {
                 if wait {
-                    sdk.wait_for_execution_completion(&execution_id)
+                    let sdk = sdk.with_completion_hooks(hooks.into());
+                    resume_hint(
+                        sdk.wait_for_execution_completion(&execution_id),
+                        "execution",
+                        &execution_id,
+                        &format!("cargo axiom run status --execution-id {execution_id} --wait"),
+                    )
                 } else {
                     let execution_status = sdk.get_execution_status(&execution_id)?;
                     Self::print_execution_status(&execution_status);
@@ -87,39 +181,41 @@ impl RunCmd {
             }
             Some(RunSubcommand::List {
                 program_id,
+                project,
                 page,
                 page_size,
+                filters,
+                mode,
             }) => {
-                let response = sdk.list_executions(&program_id, Some(page), Some(page_size))?;
+                // Show which program each item belongs to when listing across programs
+                let default_columns: &[&str] = if program_id.is_some() {
+                    &["id", "status", "mode", "created_at"]
+                } else {
+                    &["id", "status", "mode", "created_at", "program"]
+                };
+                let columns = select_columns(
+                    filters.columns.as_deref(),
+                    &EXECUTION_COLUMNS,
+                    default_columns,
+                )?;
+                let query = ExecutionListQuery {
+                    program_id,
+                    project_id: project,
+                    status: filters.status,
+                    mode,
+                    created_by: filters.created_by,
+                    created_after: filters.created_after,
+                    created_before: filters.created_before,
+                    sort: filters.sort,
+                };
+                let response = sdk.search_executions(Some(page), Some(page_size), &query)?;
 
                 if response.items.is_empty() {
                     println!("No executions found");
                     return Ok(());
                 }
 
-                // Create a new table
-                let mut table = comfy_table::Table::new();
-                table.set_header(["ID", "Status", "Mode", "Created At"]);
-
-                // Add rows to the table
-                for execution_status in response.items {
-                    let get_value = |s: &str| {
-                        if s.is_empty() {
-                            "-".to_string()
-                        } else {
-                            s.to_string()
-                        }
-                    };
-                    let id = get_value(&execution_status.id);
-                    let status = get_value(&execution_status.status);
-                    let mode = get_value(&execution_status.mode);
-                    let created_at = get_value(&execution_status.created_at);
-
-                    table.add_row([id, status, mode, created_at]);
-                }
-
-                // Print the table
-                println!("{table}");
+                println!("{}", build_table(&columns, &response.items));
 
                 let pagination = &response.pagination;
                 println!(
@@ -129,11 +225,31 @@ impl RunCmd {
 
                 Ok(())
             }
-            Some(RunSubcommand::Logs { execution_id }) => sdk.get_execution_logs(&execution_id),
+            Some(RunSubcommand::Logs {
+                execution_id,
+                follow_args,
+            }) => {
+                if follow_args.follow {
+                    resume_hint(
+                        sdk.follow_execution_logs(
+                            &execution_id,
+                            &follow_args.filter(),
+                            follow_args.save,
+                        ),
+                        "execution",
+                        &execution_id,
+                        &format!("cargo axiom run logs --execution-id {execution_id} --follow"),
+                    )
+                } else {
+                    sdk.get_execution_logs(&execution_id)
+                }
+            }
             None => {
                 use crate::progress::CliProgressCallback;
                 let callback = CliProgressCallback::new();
-                let sdk = sdk.with_callback(callback);
+                let sdk = sdk
+                    .with_callback(callback)
+                    .with_completion_hooks(self.run_args.hooks.into());
                 let args = axiom_sdk::run::RunArgs {
                     program_id: self.run_args.program_id,
                     input: self.run_args.input,
@@ -142,7 +258,12 @@ impl RunCmd {
                 let execution_id = sdk.execute_program(args)?;
 
                 if !self.run_args.detach {
-                    sdk.wait_for_execution_completion(&execution_id)
+                    resume_hint(
+                        sdk.wait_for_execution_completion(&execution_id),
+                        "execution",
+                        &execution_id,
+                        &format!("cargo axiom run status --execution-id {execution_id} --wait"),
+                    )
                 } else {
                     println!("Execution started successfully! ID: {}", execution_id);
                     println!(
@@ -176,6 +297,8 @@ impl RunCmd {
             Formatter::print_field("Error", error_message);
         }
 
+        Formatter::print_timeline(&status.into());
+
         // Show mode-specific statistics
         match status.mode.as_str() {
             "meter" => {
@@ -4,7 +4,9 @@ use axiom_sdk::{AxiomSdk, verify::VerifySdk};
 use clap::{Args, Subcommand};
 use eyre::Result;
 
-use crate::{formatting::Formatter, progress::CliProgressCallback};
+use crate::{
+    formatting::Formatter, hooks::HookArgs, interrupt::resume_hint, progress::CliProgressCallback,
+};
 
 #[derive(Args, Debug)]
 pub struct VerifyCmd {
@@ -27,6 +29,9 @@ enum VerifySubcommand {
         /// Run in detached mode (don't wait for completion)
         #[clap(long)]
         detach: bool,
+
+        #[command(flatten)]
+        hooks: HookArgs,
     },
     /// Verify a STARK proof
     Stark {
@@ -41,6 +46,9 @@ enum VerifySubcommand {
         /// Run in detached mode (don't wait for completion)
         #[clap(long)]
         detach: bool,
+
+        #[command(flatten)]
+        hooks: HookArgs,
     },
     /// Check the status of a verification
     Status {
@@ -51,6 +59,9 @@ enum VerifySubcommand {
         /// Wait for the verification to complete
         #[clap(long)]
         wait: bool,
+
+        #[command(flatten)]
+        hooks: HookArgs,
     },
 }
 
@@ -65,14 +76,22 @@ impl VerifyCmd {
                 config_id,
                 proof,
                 detach,
+                hooks,
             } => {
                 use crate::progress::CliProgressCallback;
                 let callback = CliProgressCallback::new();
-                let sdk = sdk.with_callback(callback);
+                let sdk = sdk
+                    .with_callback(callback)
+                    .with_completion_hooks(hooks.into());
                 let verify_id = sdk.verify_evm(config_id.as_deref(), proof)?;
 
                 if !detach {
-                    sdk.wait_for_evm_verify_completion(&verify_id)
+                    resume_hint(
+                        sdk.wait_for_evm_verify_completion(&verify_id),
+                        "verification",
+                        &verify_id,
+                        &resume_command(&verify_id),
+                    )
                 } else {
                     println!(
                         "To check the verification status, run: cargo axiom verify status --verify-id {verify_id}"
@@ -84,14 +103,22 @@ impl VerifyCmd {
                 program_id,
                 proof,
                 detach,
+                hooks,
             } => {
                 use crate::progress::CliProgressCallback;
                 let callback = CliProgressCallback::new();
-                let sdk = sdk.with_callback(callback);
+                let sdk = sdk
+                    .with_callback(callback)
+                    .with_completion_hooks(hooks.into());
                 let verify_id = sdk.verify_stark(&program_id, proof)?;
 
                 if !detach {
-                    sdk.wait_for_stark_verify_completion(&verify_id)
+                    resume_hint(
+                        sdk.wait_for_stark_verify_completion(&verify_id),
+                        "verification",
+                        &verify_id,
+                        &resume_command(&verify_id),
+                    )
                 } else {
                     println!(
                         "To check the verification status, run: cargo axiom verify status --verify-id {verify_id}"
@@ -99,9 +126,19 @@ impl VerifyCmd {
                     Ok(())
                 }
             }
-            VerifySubcommand::Status { verify_id, wait } => {
+            VerifySubcommand::Status {
+                verify_id,
+                wait,
+                hooks,
+            } => {
                 if wait {
-                    sdk.wait_for_verify_completion(&verify_id)
+                    let sdk = sdk.with_completion_hooks(hooks.into());
+                    resume_hint(
+                        sdk.wait_for_verify_completion(&verify_id),
+                        "verification",
+                        &verify_id,
+                        &resume_command(&verify_id),
+                    )
                 } else {
                     let verify_status = sdk.get_verification_result(&verify_id)?;
                     Self::print_verify_status(&verify_status);
@@ -124,3 +161,7 @@ impl VerifyCmd {
         Formatter::print_field("Created At", &status.created_at);
     }
 }
+
+fn resume_command(verify_id: &str) -> String {
+    format!("cargo axiom verify status --verify-id {verify_id} --wait")
+}
@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use axiom_sdk::{
+    AxiomSdk, NoopCallback, build::BuildSdk, format_duration, is_not_found, poll_sleep,
+    prove::ProveSdk, run::RunSdk, start_wait, timeline::Timeline, verify::VerifySdk,
+};
+use clap::Parser;
+use eyre::Result;
+use indicatif::{MultiProgress, ProgressBar};
+
+use crate::formatting::Formatter;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "watch",
+    about = "Watch programs, proofs, executions and verifications until they finish"
+)]
+pub struct WatchCmd {
+    /// IDs of programs, proofs, executions or verifications to watch
+    #[clap(value_name = "ID", required = true)]
+    ids: Vec<String>,
+
+    /// Seconds between status checks
+    #[clap(long, value_name = "SECS", default_value = "5")]
+    interval: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JobKind {
+    Program,
+    Proof,
+    Execution,
+    Verification,
+}
+
+impl std::fmt::Display for JobKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobKind::Program => write!(f, "program"),
+            JobKind::Proof => write!(f, "proof"),
+            JobKind::Execution => write!(f, "execution"),
+            JobKind::Verification => write!(f, "verification"),
+        }
+    }
+}
+
+/// Status of a job, common to all kinds
+struct JobSnapshot {
+    state: String,
+    timeline: Timeline,
+    /// `Some(true)` if the job succeeded, `Some(false)` if it failed, `None` while running
+    outcome: Option<bool>,
+}
+
+struct Job {
+    id: String,
+    kind: JobKind,
+    bar: ProgressBar,
+    outcome: Option<bool>,
+}
+
+impl WatchCmd {
+    pub fn run(self) -> Result<()> {
+        let config = axiom_sdk::load_config()?;
+        let sdk = AxiomSdk::new(config);
+
+        let multi = MultiProgress::new();
+        let mut jobs = Vec::new();
+        for id in self.ids {
+            let (kind, snapshot) = detect_job(&sdk, &id)?;
+            let bar = multi.add(Formatter::create_spinner(&job_message(
+                &id, kind, &snapshot,
+            )));
+            let bar = match snapshot.timeline.total_time() {
+                Some(elapsed) => bar.with_elapsed(elapsed),
+                None => bar,
+            };
+            let mut job = Job {
+                id,
+                kind,
+                bar,
+                outcome: None,
+            };
+            update_job(&mut job, &snapshot);
+            jobs.push(job);
+        }
+
+        let _wait = start_wait();
+        while jobs.iter().any(|job| job.outcome.is_none()) {
+            if let Err(e) = poll_sleep(Duration::from_secs(self.interval), &NoopCallback) {
+                let running: Vec<&str> = jobs
+                    .iter()
+                    .filter(|job| job.outcome.is_none())
+                    .map(|job| {
+                        job.bar.abandon();
+                        job.id.as_str()
+                    })
+                    .collect();
+                println!();
+                Formatter::print_warning(
+                    "Stopped watching. The jobs keep running on the Axiom Proving Service.",
+                );
+                Formatter::print_info(&format!(
+                    "To resume, run: cargo axiom watch {}",
+                    running.join(" ")
+                ));
+                return Err(e);
+            }
+            for job in jobs.iter_mut().filter(|job| job.outcome.is_none()) {
+                match fetch_job(&sdk, &job.id, job.kind) {
+                    Ok(snapshot) => update_job(job, &snapshot),
+                    // Keep watching through transient errors
+                    Err(e) => job.bar.set_message(format!(
+                        "{:<12} {}  (status check failed: {})",
+                        job.kind, job.id, e
+                    )),
+                }
+            }
+        }
+
+        let failed = jobs.iter().filter(|job| job.outcome == Some(false)).count();
+        if failed > 0 {
+            eyre::bail!("{} of {} jobs failed", failed, jobs.len());
+        }
+        Formatter::print_success(&format!("All {} jobs succeeded", jobs.len()));
+        Ok(())
+    }
+}
+
+/// Find out what kind of job `id` belongs to by asking each endpoint in turn. Only a
+/// "not found" moves on to the next kind; other errors are returned.
+fn detect_job(sdk: &AxiomSdk, id: &str) -> Result<(JobKind, JobSnapshot)> {
+    for kind in [
+        JobKind::Proof,
+        JobKind::Execution,
+        JobKind::Program,
+        JobKind::Verification,
+    ] {
+        match fetch_job(sdk, id, kind) {
+            Ok(snapshot) => return Ok((kind, snapshot)),
+            Err(e) if is_not_found(&e) => continue,
+            Err(e) => return Err(e.wrap_err(format!("Failed to look up {id}"))),
+        }
+    }
+    eyre::bail!("No program, proof, execution or verification found with ID {id}")
+}
+
+fn fetch_job(sdk: &AxiomSdk, id: &str, kind: JobKind) -> Result<JobSnapshot> {
+    Ok(match kind {
+        JobKind::Program => {
+            let status = sdk.get_build_status(id)?;
+            JobSnapshot {
+                timeline: Timeline::from(&status),
+                outcome: match status.status.as_str() {
+                    "ready" => Some(true),
+                    "error" | "failed" => Some(false),
+                    _ => None,
+                },
+                state: status.status,
+            }
+        }
+        JobKind::Proof => {
+            let status = sdk.get_proof_status(id)?;
+            JobSnapshot {
+                timeline: Timeline::from(&status),
+                outcome: match status.state.as_str() {
+                    "Succeeded" => Some(true),
+                    "Failed" | "Canceled" => Some(false),
+                    _ => None,
+                },
+                state: status.state,
+            }
+        }
+        JobKind::Execution => {
+            let status = sdk.get_execution_status(id)?;
+            JobSnapshot {
+                timeline: Timeline::from(&status),
+                outcome: match status.status.as_str() {
+                    "Succeeded" => Some(true),
+                    "Failed" => Some(false),
+                    _ => None,
+                },
+                state: status.status,
+            }
+        }
+        JobKind::Verification => {
+            let status = sdk.get_verification_result(id)?;
+            JobSnapshot {
+                timeline: Timeline::from(&status),
+                outcome: match status.result.as_str() {
+                    "verified" => Some(true),
+                    "failed" => Some(false),
+                    _ => None,
+                },
+                state: status.result,
+            }
+        }
+    })
+}
+
+fn update_job(job: &mut Job, snapshot: &JobSnapshot) {
+    let message = job_message(&job.id, job.kind, snapshot);
+    job.outcome = snapshot.outcome;
+    match snapshot.outcome {
+        Some(true) => job.bar.finish_with_message(format!("✓ {message}")),
+        Some(false) => job.bar.finish_with_message(format!("✗ {message}")),
+        None => job.bar.set_message(message),
+    }
+}
+
+fn job_message(id: &str, kind: JobKind, snapshot: &JobSnapshot) -> String {
+    let mut message = format!("{:<12} {}  {}", kind, id, snapshot.state);
+    if !snapshot.timeline.is_ongoing()
+        && let Some(duration) = snapshot.timeline.total_time()
+    {
+        message.push_str(&format!(
+            " in {}",
+            format_duration(duration.as_secs() as i64)
+        ));
+    }
+    message
+}
@@ -1,5 +1,6 @@
 use std::{io::Write, time::Duration};
 
+use axiom_sdk::{format_duration, timeline::Timeline};
 use console::{Term, style};
 use indicatif::{ProgressBar, ProgressStyle};
 
@@ -42,6 +43,35 @@ impl Formatter {
         println!("  {}: {}", style(key).dim(), value);
     }
 
+    /// Print how long a job was queued and ran for, or has been so far if it is not done
+    pub fn print_timeline(timeline: &Timeline) {
+        let format = |duration: Duration, ongoing: bool| {
+            let formatted = format_duration(duration.as_secs() as i64);
+            if ongoing {
+                format!("{formatted} so far")
+            } else {
+                formatted
+            }
+        };
+        let queue_time = timeline.queue_time();
+        let run_time = timeline.run_time();
+
+        Self::print_section("Timeline");
+        if let Some(queue_time) = queue_time {
+            let queued = timeline.is_ongoing() && timeline.launched_at.is_none();
+            Self::print_field("Queue Time", &format(queue_time, queued));
+        }
+        if let Some(run_time) = run_time {
+            Self::print_field("Run Time", &format(run_time, timeline.is_ongoing()));
+        }
+        if let (Some(queue_time), Some(run_time)) = (queue_time, run_time) {
+            Self::print_field(
+                "Total Time",
+                &format(queue_time + run_time, timeline.is_ongoing()),
+            );
+        }
+    }
+
     /// Print a status update that overwrites the current line
     pub fn print_status(text: &str) {
         let term = Term::stdout();
@@ -0,0 +1,25 @@
+//! `--on-complete` and `--notify-url` flags shared by the commands that wait on jobs.
+
+use axiom_sdk::hooks::CompletionHooks;
+use clap::Args;
+
+#[derive(Debug, Args)]
+pub struct HookArgs {
+    /// Shell command to run when the job finishes. It gets the job's status JSON on stdin
+    /// and in $AXIOM_JOB_STATUS, and its ID and state in $AXIOM_JOB_ID and $AXIOM_JOB_STATE
+    #[arg(long, value_name = "COMMAND")]
+    pub on_complete: Option<String>,
+
+    /// URL to POST the job's status JSON to when the job finishes
+    #[arg(long, value_name = "URL")]
+    pub notify_url: Option<String>,
+}
+
+impl From<HookArgs> for CompletionHooks {
+    fn from(args: HookArgs) -> Self {
+        CompletionHooks {
+            on_complete: args.on_complete,
+            notify_url: args.notify_url,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Ctrl-C handling for commands that wait on jobs.
+
+use std::io::{self, IsTerminal, Write};
+
+use axiom_sdk::{AxiomSdk, Interrupted, prove::ProveSdk};
+use eyre::Result;
+
+use crate::formatting::Formatter;
+
+/// Exit code of a process stopped by SIGINT
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Make the first Ctrl-C during a wait stop it cleanly. Outside of waits, and on a second
+/// Ctrl-C, the process exits immediately like it would without a handler.
+pub fn install_handler() {
+    let result = ctrlc::set_handler(|| {
+        if !axiom_sdk::is_waiting() || axiom_sdk::is_interrupted() {
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+        axiom_sdk::interrupt();
+    });
+    if let Err(e) = result {
+        eprintln!("Warning: failed to install Ctrl-C handler: {e}");
+    }
+}
+
+pub fn is_interrupted(err: &eyre::Report) -> bool {
+    err.downcast_ref::<Interrupted>().is_some()
+}
+
+/// If the wait that produced `result` was interrupted, print how to resume it
+pub fn resume_hint<T>(result: Result<T>, job: &str, id: &str, resume_command: &str) -> Result<T> {
+    if let Err(e) = &result
+        && is_interrupted(e)
+    {
+        println!();
+        Formatter::print_warning(&format!(
+            "Stopped waiting for {job} {id}, which keeps running on the Axiom Proving Service"
+        ));
+        Formatter::print_info(&format!("To resume, run: {resume_command}"));
+    }
+    result
+}
+
+/// [`resume_hint`] for proofs. If the proof is still queued or running, it is also
+/// canceled when `cancel` is set, or when the user confirms.
+pub fn handle_proof_interrupt<T>(
+    sdk: &AxiomSdk,
+    result: Result<T>,
+    proof_id: &str,
+    cancel: bool,
+) -> Result<T> {
+    let result = resume_hint(
+        result,
+        "proof",
+        proof_id,
+        &format!("cargo axiom prove status --proof-id {proof_id} --wait"),
+    );
+    let Err(e) = &result else {
+        return result;
+    };
+    if !is_interrupted(e) {
+        return result;
+    }
+
+    let still_running = sdk
+        .get_proof_status(proof_id)
+        .is_ok_and(|status| matches!(status.state.as_str(), "Queued" | "InProgress"));
+    if still_running && (cancel || confirm(&format!("Cancel proof {proof_id}?"))) {
+        match sdk.cancel_proof(proof_id) {
+            Ok(message) => Formatter::print_success(&message),
+            Err(e) => Formatter::print_error(&format!("Failed to cancel proof {proof_id}: {e}")),
+        }
+    }
+    result
+}
+
+/// Ask a yes/no question, defaulting to no when stdin is not a terminal
+pub fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+    print!("{question} [y/N]: ");
+    let _ = io::stdout().flush();
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).is_err() {
+        return false;
+    }
+    matches!(input.trim(), "y" | "Y" | "yes")
+}
@@ -0,0 +1,170 @@
+//! Filters and column selection shared by the `list` subcommands.
+
+use std::time::Duration;
+
+use axiom_sdk::{calculate_duration, format_duration, timeline::Timeline};
+use chrono::{DateTime, NaiveDate};
+use clap::Args;
+use comfy_table::Table;
+use eyre::Result;
+
+#[derive(Debug, Args)]
+pub struct ListFilterArgs {
+    /// Only show items with this status
+    #[arg(long, value_name = "STATUS")]
+    pub status: Option<String>,
+
+    /// Only show items created by this user
+    #[arg(long, value_name = "USER")]
+    pub created_by: Option<String>,
+
+    /// Only show items created after this date (YYYY-MM-DD or RFC3339)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_filter)]
+    pub created_after: Option<String>,
+
+    /// Only show items created before this date (YYYY-MM-DD or RFC3339)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_filter)]
+    pub created_before: Option<String>,
+
+    /// Field to sort by, prefixed with '-' for descending order (e.g. "-created_at")
+    #[arg(long, value_name = "FIELD", allow_hyphen_values = true)]
+    pub sort: Option<String>,
+
+    /// Comma-separated list of columns to show (e.g. "id,status,cells_used,duration")
+    #[arg(long, value_name = "COLUMNS")]
+    pub columns: Option<String>,
+}
+
+/// Accept a plain date (midnight UTC) or an RFC3339 timestamp, normalized to RFC3339
+pub fn parse_date_filter(s: &str) -> Result<String, String> {
+    if DateTime::parse_from_rfc3339(s).is_ok() {
+        return Ok(s.to_string());
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|date| format!("{date}T00:00:00Z"))
+        .map_err(|_| format!("invalid date '{s}', expected YYYY-MM-DD or an RFC3339 timestamp"))
+}
+
+/// A column that can be shown in a listing table
+pub struct Column<T> {
+    /// Name used in `--columns`
+    pub name: &'static str,
+    pub header: &'static str,
+    pub value: fn(&T) -> String,
+}
+
+/// Resolve a `--columns` value against the available columns, or use the default ones
+pub fn select_columns<'a, T>(
+    spec: Option<&str>,
+    available: &'a [Column<T>],
+    default: &[&str],
+) -> Result<Vec<&'a Column<T>>> {
+    let names: Vec<&str> = match spec {
+        Some(spec) => spec
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect(),
+        None => default.to_vec(),
+    };
+    names
+        .into_iter()
+        .map(|name| {
+            available
+                .iter()
+                .find(|column| column.name == name)
+                .ok_or_else(|| {
+                    eyre::eyre!(
+                        "Unknown column '{}'. Available columns: {}",
+                        name,
+                        available
+                            .iter()
+                            .map(|column| column.name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })
+        })
+        .collect()
+}
+
+pub fn build_table<T>(columns: &[&Column<T>], items: &[T]) -> Table {
+    let mut table = Table::new();
+    table.set_header(columns.iter().map(|column| column.header));
+    for item in items {
+        table.add_row(columns.iter().map(|column| (column.value)(item)));
+    }
+    table
+}
+
+pub fn or_dash(s: &str) -> String {
+    if s.is_empty() {
+        "-".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+/// A duration like "1h 2m 3s", or "-" if it is unknown
+pub fn format_optional_duration(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".to_string(), |d| format_duration(d.as_secs() as i64))
+}
+
+/// How long a finished job ran, or "-" if it has not finished
+pub fn duration(timeline: Timeline) -> String {
+    match (&timeline.launched_at, &timeline.terminated_at) {
+        (Some(start), Some(end)) => calculate_duration(start, end).unwrap_or_else(|_| "-".into()),
+        _ => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Item(&'static str);
+
+    const COLUMNS: [Column<Item>; 2] = [
+        Column {
+            name: "id",
+            header: "ID",
+            value: |item| item.0.to_string(),
+        },
+        Column {
+            name: "len",
+            header: "Length",
+            value: |item| item.0.len().to_string(),
+        },
+    ];
+
+    #[test]
+    fn test_parse_date_filter() {
+        assert_eq!(
+            parse_date_filter("2025-03-01").unwrap(),
+            "2025-03-01T00:00:00Z"
+        );
+        assert_eq!(
+            parse_date_filter("2025-03-01T12:00:00+02:00").unwrap(),
+            "2025-03-01T12:00:00+02:00"
+        );
+        assert!(parse_date_filter("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_select_columns() {
+        let default = select_columns(None, &COLUMNS, &["id"]).unwrap();
+        assert_eq!(default.len(), 1);
+        assert_eq!(default[0].name, "id");
+
+        let selected = select_columns(Some("len, id"), &COLUMNS, &["id"]).unwrap();
+        let names: Vec<_> = selected.iter().map(|column| column.name).collect();
+        assert_eq!(names, ["len", "id"]);
+
+        let err = select_columns(Some("id,size"), &COLUMNS, &["id"])
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("Unknown column 'size'"));
+        assert!(err.contains("id, len"));
+    }
+}
@@ -0,0 +1,86 @@
+//! `--follow` flags shared by the `logs` subcommands of proofs and executions.
+
+use axiom_sdk::logs::LogFilter;
+use chrono::{DateTime, Duration, Utc};
+use clap::Args;
+
+use crate::listing::parse_date_filter;
+
+#[derive(Debug, Args)]
+pub struct FollowLogsArgs {
+    /// Print the logs as they are written until the job finishes
+    #[arg(long)]
+    pub follow: bool,
+
+    /// Only print lines logged since this time: a duration ago (e.g. "30s", "10m", "2h",
+    /// "1d") or a date (YYYY-MM-DD or RFC3339)
+    #[arg(long, value_name = "TIME", requires = "follow", value_parser = parse_since)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only print lines containing this text
+    #[arg(long, value_name = "TEXT", requires = "follow")]
+    pub grep: Option<String>,
+
+    /// Save the complete logs to the artifacts directory when the job finishes
+    #[arg(long, requires = "follow")]
+    pub save: bool,
+}
+
+impl FollowLogsArgs {
+    pub fn filter(&self) -> LogFilter {
+        LogFilter {
+            since: self.since,
+            grep: self.grep.clone(),
+        }
+    }
+}
+
+fn parse_since(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Some(ago) = parse_duration(s) {
+        return Utc::now()
+            .checked_sub_signed(ago)
+            .ok_or_else(|| format!("invalid time '{s}', the duration is too long"));
+    }
+    let date = parse_date_filter(s)
+        .map_err(|_| format!("invalid time '{s}', expected a duration like 10m or a date"))?;
+    DateTime::parse_from_rfc3339(&date)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|e| e.to_string())
+}
+
+/// Parse a duration like "30s", "10m", "2h" or "1d". Durations too long to represent
+/// are `None`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (split, unit) = s.char_indices().last()?;
+    let amount: i64 = s[..split].parse().ok()?;
+    match unit {
+        's' => Duration::try_seconds(amount),
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(parse_duration("10m"), Some(Duration::minutes(10)));
+        assert_eq!(parse_duration("2h"), Some(Duration::hours(2)));
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("2025-06-01"), None);
+        assert_eq!(parse_duration("5é"), None);
+        assert_eq!(parse_duration("99999999999999d"), None);
+        assert!(parse_since("5é").is_err());
+        assert!(parse_since("99999999999999d").is_err());
+        assert!(parse_since("9000000000000s").is_err());
+        assert_eq!(
+            parse_since("2025-06-01").unwrap().to_rfc3339(),
+            "2025-06-01T00:00:00+00:00"
+        );
+        assert!(parse_since("yesterday").is_err());
+    }
+}
@@ -8,11 +8,15 @@ use eyre::Result;
 
 mod commands;
 mod formatting;
+mod hooks;
+mod interrupt;
+mod listing;
+mod logs;
 mod progress;
 
 use commands::{
-    BuildCmd, ConfigCmd, InitCmd, ProjectsCmd, ProveCmd, RegisterCmd, RunCmd, UploadExeCmd,
-    VerifyCmd, VersionCmd,
+    BuildCmd, BundleCmd, ConfigCmd, DecodeCmd, InitCmd, PipelineCmd, ProjectsCmd, ProofCmd,
+    ProveCmd, RegisterCmd, RunCmd, UploadExeCmd, VerifyCmd, VersionCmd, WatchCmd,
 };
 
 #[derive(Parser)]
@@ -40,7 +44,7 @@ enum AxiomCommands {
     /// Register Axiom API credentials
     Register(RegisterCmd),
     /// Upload a locally-built program (ELF + VMEXE) to the Axiom Proving Service
-    Build(BuildCmd),
+    Build(Box<BuildCmd>),
     /// Generate a proof using the Axiom Proving Service
     Prove(ProveCmd),
     /// Execute a program using the Axiom Execution Service
@@ -49,6 +53,16 @@ enum AxiomCommands {
     Config(ConfigCmd),
     /// Verify a proof using the Axiom Verifying Service
     Verify(VerifyCmd),
+    /// Decode data produced by programs, such as their public values
+    Decode(DecodeCmd),
+    /// Work with downloaded proofs, such as encoding them for on-chain verification
+    Proof(ProofCmd),
+    /// Check proof bundles created with `prove bundle`
+    Bundle(BundleCmd),
+    /// Build, execute, prove, download and verify a program in one go
+    Pipeline(PipelineCmd),
+    /// Watch programs, proofs, executions and verifications until they finish
+    Watch(WatchCmd),
     /// Manage projects
     Projects(ProjectsCmd),
     /// Upload pre-built VMEXE to Axiom Proving Service
@@ -141,6 +155,8 @@ fn main() {
     // Make CLI version available to the SDK for request headers
     set_cli_version(env!("CARGO_PKG_VERSION"));
 
+    interrupt::install_handler();
+
     let Cargo::Axiom(args) = Cargo::parse();
 
     let result = match args.command {
@@ -151,6 +167,11 @@ fn main() {
         AxiomCommands::Run(cmd) => cmd.run(),
         AxiomCommands::Config(cmd) => cmd.run(),
         AxiomCommands::Verify(cmd) => cmd.run(),
+        AxiomCommands::Decode(cmd) => cmd.run(),
+        AxiomCommands::Proof(cmd) => cmd.run(),
+        AxiomCommands::Bundle(cmd) => cmd.run(),
+        AxiomCommands::Pipeline(cmd) => cmd.run(),
+        AxiomCommands::Watch(cmd) => cmd.run(),
         AxiomCommands::Projects(cmd) => cmd.run(),
         AxiomCommands::UploadExe(cmd) => cmd.run(),
         AxiomCommands::Version(cmd) => cmd.run(),
@@ -161,6 +182,10 @@ fn main() {
     };
 
     if let Err(err) = result {
+        // How to resume was already printed by the interrupted command
+        if interrupt::is_interrupted(&err) {
+            process::exit(interrupt::INTERRUPTED_EXIT_CODE);
+        }
         if args.debug {
             // In debug mode, print the full error with backtrace
             eprintln!("Error: {err:?}");
@@ -0,0 +1,489 @@
+//! Breaking proof logs down into phases, timings and known causes of failure.
+
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use chrono::{DateTime, Utc};
+use eyre::{Context, Result};
+
+use crate::{
+    AxiomSdk, ProgressCallback, authenticated_get, download_file,
+    logs::parse_log_timestamp,
+    prove::{ProofStatus, ProveSdk, proof_artifacts_dir},
+    timeline::Timeline,
+};
+
+pub trait AnalyzeSdk {
+    /// Fetch the status and logs of a proof and break them down into phases, timings
+    /// and peak resource usage. The logs are saved to the proof's artifacts directory.
+    fn analyze_proof(&self, proof_id: &str) -> Result<ProofAnalysis>;
+}
+
+#[derive(Debug)]
+pub struct ProofAnalysis {
+    pub proof: ProofStatus,
+    pub logs_path: PathBuf,
+    /// Time between submission and launch
+    pub queue_time: Option<Duration>,
+    /// Time between launch and termination
+    pub run_time: Option<Duration>,
+    pub logs: LogAnalysis,
+    /// Known cause of failure, if the proof failed
+    pub failure: Option<FailureCause>,
+}
+
+#[derive(Debug, Default)]
+pub struct LogAnalysis {
+    /// Phases in the order they started
+    pub phases: Vec<Span<ProofPhase>>,
+    /// Segments in order of their index
+    pub segments: Vec<Span<usize>>,
+    /// Highest memory usage reported in the logs, in bytes
+    pub peak_memory: Option<u64>,
+}
+
+impl LogAnalysis {
+    /// Time spent executing the program, as logged by the prover
+    pub fn execution_time(&self) -> Option<Duration> {
+        self.phases
+            .iter()
+            .find(|span| span.item == ProofPhase::Execution)
+            .map(Span::duration)
+    }
+
+    /// Time from the start of the first proving phase to the end of the last one
+    pub fn proving_time(&self) -> Option<Duration> {
+        let proving = || {
+            self.phases
+                .iter()
+                .filter(|span| span.item != ProofPhase::Execution)
+        };
+        let started_at = proving().map(|span| span.started_at).min()?;
+        let ended_at = proving().map(|span| span.ended_at).max()?;
+        (ended_at - started_at).to_std().ok()
+    }
+}
+
+/// When something first and last appeared in the logs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span<T> {
+    pub item: T,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+impl<T> Span<T> {
+    pub fn duration(&self) -> Duration {
+        (self.ended_at - self.started_at)
+            .to_std()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProofPhase {
+    Execution,
+    AppProof,
+    Leaf,
+    Internal,
+    Root,
+    Wrapper,
+}
+
+impl std::fmt::Display for ProofPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProofPhase::Execution => write!(f, "Execution"),
+            ProofPhase::AppProof => write!(f, "App proof"),
+            ProofPhase::Leaf => write!(f, "Leaf aggregation"),
+            ProofPhase::Internal => write!(f, "Internal aggregation"),
+            ProofPhase::Root => write!(f, "Root proof"),
+            ProofPhase::Wrapper => write!(f, "Halo2 wrapper"),
+        }
+    }
+}
+
+impl ProofPhase {
+    /// The phase of a `group` span field of the prover's tracing output. Internal
+    /// aggregation is grouped by tree height, as in `internal.0`.
+    fn from_group(group: &str) -> Option<Self> {
+        match group {
+            "app_proof" => Some(ProofPhase::AppProof),
+            "leaf" => Some(ProofPhase::Leaf),
+            "root" => Some(ProofPhase::Root),
+            "halo2_outer" | "halo2_wrapper" => Some(ProofPhase::Wrapper),
+            group if group.starts_with("internal") => Some(ProofPhase::Internal),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureCause {
+    InvalidInput,
+    OutOfMemory,
+    CycleLimit,
+    GuestPanic,
+}
+
+impl FailureCause {
+    /// Causes in the order they are checked. A guest panic also ends execution with a
+    /// non-zero exit code, so it is checked last.
+    const ALL: [FailureCause; 4] = [
+        FailureCause::InvalidInput,
+        FailureCause::OutOfMemory,
+        FailureCause::CycleLimit,
+        FailureCause::GuestPanic,
+    ];
+
+    /// Whether a line of the error message or the logs shows this cause, matched on the
+    /// exact messages of the prover and its runtime
+    fn shown_by(&self, line: &str) -> bool {
+        match self {
+            // The guest read more input than was given: OpenVM's hint input phantom
+            // fails with `EndOfInputStream`
+            FailureCause::InvalidInput => line.contains("EndOfInputStream"),
+            FailureCause::OutOfMemory => {
+                // Kubernetes termination reason
+                line.contains("OOMKilled")
+                    // Rust's "memory allocation of N bytes failed"
+                    || (line.contains("memory allocation of ") && line.contains(" bytes failed"))
+                    // ENOMEM as formatted by `std::io::Error`
+                    || line.contains("Cannot allocate memory (os error 12)")
+            }
+            // OpenVM's `ExecutionError::DidNotTerminate`, when the instruction limit
+            // is reached before the program terminates
+            FailureCause::CycleLimit => line.contains("program must terminate"),
+            FailureCause::GuestPanic => {
+                // OpenVM's `ExecutionError::FailedWithExitCode`, as the guest's panic
+                // handler terminates with exit code 1
+                line.split("program exit code ")
+                    .skip(1)
+                    .any(|rest| rest.split(|c: char| !c.is_ascii_digit()).next() == Some("1"))
+                    // The guest's panic message. Host panics are prefixed with the
+                    // thread, as in "thread 'main' panicked at".
+                    || line
+                        .match_indices("panicked at ")
+                        .any(|(i, _)| !line[..i].ends_with("' "))
+            }
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            FailureCause::InvalidInput => "the program read more input than was given",
+            FailureCause::OutOfMemory => "proving ran out of memory",
+            FailureCause::CycleLimit => "the program exceeded the cycle limit",
+            FailureCause::GuestPanic => "the guest program panicked",
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            FailureCause::InvalidInput => {
+                "Check that the input has every value the program reads, in order: hex strings starting with 01 (bytes) or 02 (field elements), or JSON files of the form {\"input\": [\"0x01...\"]}"
+            }
+            FailureCause::OutOfMemory => {
+                "Retry with more GPUs (--num-gpus), or split the work across several proofs with smaller inputs"
+            }
+            FailureCause::CycleLimit => {
+                "Check the program for unbounded loops, and measure its cycles with `cargo axiom run --mode meter`"
+            }
+            FailureCause::GuestPanic => {
+                "Find the panic message in the logs, and reproduce it without proving with `cargo axiom run` on the same input"
+            }
+        }
+    }
+}
+
+impl AnalyzeSdk for AxiomSdk {
+    fn analyze_proof(&self, proof_id: &str) -> Result<ProofAnalysis> {
+        let proof = self.get_proof_status(proof_id)?;
+        let logs_path = proof_artifacts_dir(&proof.program_uuid, proof_id).join("logs.txt");
+        self.save_proof_logs_to_path(proof_id, logs_path.clone())?;
+        let logs = std::fs::read(&logs_path)
+            .with_context(|| format!("Failed to read {}", logs_path.display()))?;
+        let logs = String::from_utf8_lossy(&logs);
+
+        let failure = if proof.state == "Failed" {
+            diagnose_failure(proof.error_message.as_deref(), &logs)
+        } else {
+            None
+        };
+        let timeline = Timeline::from(&proof);
+        Ok(ProofAnalysis {
+            queue_time: timeline.queue_time(),
+            run_time: timeline.run_time(),
+            logs: analyze_logs(&logs),
+            failure,
+            proof,
+            logs_path,
+        })
+    }
+}
+
+impl AxiomSdk {
+    /// Tell the user what likely made a proof fail, if it matches a known cause.
+    /// Problems fetching the logs are ignored.
+    pub(crate) fn explain_proof_failure(
+        &self,
+        proof_status: &ProofStatus,
+        callback: &dyn ProgressCallback,
+    ) {
+        let url = format!("{}/proofs/{}/logs", self.config.api_url, proof_status.id);
+        let logs = authenticated_get(&self.config, &url)
+            .and_then(|request| download_file(request, None, "Failed to download proof logs"))
+            .map(|logs| String::from_utf8_lossy(&logs).into_owned())
+            .unwrap_or_default();
+        if let Some(cause) = diagnose_failure(proof_status.error_message.as_deref(), &logs) {
+            callback.on_warning(&format!("Likely cause: {}", cause.description()));
+            callback.on_info(&format!("Hint: {}", cause.hint()));
+        }
+    }
+}
+
+/// Match the error message, then the logs, against known causes of failure
+pub fn diagnose_failure(error_message: Option<&str>, logs: &str) -> Option<FailureCause> {
+    let find = |text: &str| {
+        FailureCause::ALL
+            .into_iter()
+            .find(|cause| text.lines().any(|line| cause.shown_by(line)))
+    };
+    error_message.and_then(find).or_else(|| find(logs))
+}
+
+pub fn analyze_logs(logs: &str) -> LogAnalysis {
+    let mut phases: BTreeMap<ProofPhase, Span<ProofPhase>> = BTreeMap::new();
+    let mut segments: BTreeMap<usize, Span<usize>> = BTreeMap::new();
+    let mut peak_memory = None;
+    // Lines without a timestamp take the timestamp of the line before them
+    let mut timestamp = None;
+
+    for line in logs.lines() {
+        timestamp = parse_log_timestamp(line).or(timestamp);
+        let lower = line.to_lowercase();
+        if lower.contains("mem") || lower.contains("rss") {
+            peak_memory = peak_memory.max(max_memory_size(&lower));
+        }
+        let Some(timestamp) = timestamp else {
+            continue;
+        };
+        // Execution runs inside the app proof group. Otherwise the innermost span with
+        // a known group is the phase the line belongs to.
+        let phase = if in_execution_span(line) {
+            Some(ProofPhase::Execution)
+        } else {
+            span_field_values(line, "group")
+                .into_iter()
+                .filter_map(ProofPhase::from_group)
+                .next_back()
+        };
+        if let Some(phase) = phase {
+            extend_span(&mut phases, phase, timestamp);
+        }
+        if let Some(index) = span_field_values(line, "segment")
+            .into_iter()
+            .find_map(|index| index.parse().ok())
+        {
+            extend_span(&mut segments, index, timestamp);
+        }
+    }
+
+    let mut phases: Vec<_> = phases.into_values().collect();
+    phases.sort_by_key(|span| span.started_at);
+    LogAnalysis {
+        phases,
+        segments: segments.into_values().collect(),
+        peak_memory,
+    }
+}
+
+fn extend_span<T: Ord + Copy>(spans: &mut BTreeMap<T, Span<T>>, item: T, at: DateTime<Utc>) {
+    spans
+        .entry(item)
+        .and_modify(|span| span.ended_at = at)
+        .or_insert(Span {
+            item,
+            started_at: at,
+            ended_at: at,
+        });
+}
+
+/// Values of `field` in the spans a line was logged in, outermost first. The prover
+/// logs with `tracing`, which prefixes each line with its spans as
+/// `name{field=value field="text"}:`, so words elsewhere in the line are not matched.
+fn span_field_values<'a>(line: &'a str, field: &str) -> Vec<&'a str> {
+    let mut values = Vec::new();
+    let mut rest = line;
+    while let Some(open) = rest.find('{') {
+        let fields = &rest[open + 1..];
+        let Some(close) = fields.find("}:") else {
+            break;
+        };
+        let named = rest[..open]
+            .chars()
+            .next_back()
+            .is_some_and(|c| !c.is_whitespace());
+        if named {
+            values.extend(
+                fields[..close]
+                    .split_whitespace()
+                    .filter_map(|pair| pair.split_once('='))
+                    .filter(|(name, _)| *name == field)
+                    .map(|(_, value)| value.trim_matches('"')),
+            );
+        }
+        rest = &fields[close + 2..];
+    }
+    values
+}
+
+/// Whether a line was logged in one of the prover's execution spans, such as
+/// `execute_metered` or `execute_preflight`. Only the spans before the module path of
+/// the line are checked, where span names are followed by their fields or by `:`.
+fn in_execution_span(line: &str) -> bool {
+    let Some(target) = line.find("::") else {
+        return false;
+    };
+    let spans = &line[..line[..target].rfind(' ').map_or(0, |i| i + 1)];
+    spans.match_indices("execute_").any(|(i, _)| {
+        let before = &spans[..i];
+        let starts_span = before.is_empty()
+            || before.ends_with(char::is_whitespace)
+            || (before.ends_with(':') && !before.ends_with("::"));
+        let rest = &spans[i..];
+        let name_len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        let after = &rest[name_len..];
+        starts_span
+            && (after.starts_with('{') || (after.starts_with(':') && !after.starts_with("::")))
+    })
+}
+
+/// Largest size like "12.5 GB" or "512MiB" in a line, in bytes
+fn max_memory_size(line: &str) -> Option<u64> {
+    let tokens: Vec<&str> = line
+        .split(|c: char| c.is_whitespace() || matches!(c, '=' | ',' | ':' | '(' | ')'))
+        .filter(|token| !token.is_empty())
+        .collect();
+    tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(i, token)| {
+            let split = token
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(token.len());
+            let (number, unit) = token.split_at(split);
+            let number: f64 = number.parse().ok()?;
+            let unit = if unit.is_empty() {
+                *tokens.get(i + 1)?
+            } else {
+                unit
+            };
+            let multiplier = match unit {
+                "kb" | "kib" => 1u64 << 10,
+                "mb" | "mib" => 1 << 20,
+                "gb" | "gib" => 1 << 30,
+                "tb" | "tib" => 1 << 40,
+                _ => return None,
+            };
+            Some((number * multiplier as f64) as u64)
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_logs() {
+        let logs = r#"
+2025-06-01T12:00:00.000000Z  INFO app proof{group="app_proof"}:execute_metered: openvm_circuit::arch::execution: starting
+2025-06-01T12:00:09.000000Z  INFO app proof{group="app_proof"}:execute_metered: openvm_circuit::arch::execution: 3 segments
+2025-06-01T12:00:10.000000Z  INFO app proof{group="app_proof"}:prove_segment{segment=0}: openvm_stark_backend::prover: trace generation done
+2025-06-01T12:00:40.000000Z  INFO app proof{group="app_proof"}:prove_segment{segment=1}: openvm_stark_backend::prover: trace generation done
+2025-06-01T12:01:00.000000Z  INFO app proof{group="app_proof"}: openvm_sdk::prover: peak memory: 41.5 GB
+2025-06-01T12:01:05.000000Z  INFO agg_layer{group="leaf" idx=0}: openvm_sdk::prover::agg: proving
+2025-06-01T12:01:35.000000Z  INFO agg_layer{group="internal.0" idx=0}: openvm_sdk::prover::agg: proving
+2025-06-01T12:02:05.000000Z  INFO root{group="root"}: openvm_sdk::prover::agg: proof done (rss=512MiB)
+2025-06-01T12:02:06.000000Z  WARN openvm_sdk::fs: reading /root/.cache/openvm/internal error log, will execute_later: no
+"#;
+        let analysis = analyze_logs(logs);
+        let phases: Vec<_> = analysis
+            .phases
+            .iter()
+            .map(|span| (span.item, span.duration().as_secs()))
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                (ProofPhase::Execution, 9),
+                (ProofPhase::AppProof, 50),
+                (ProofPhase::Leaf, 0),
+                (ProofPhase::Internal, 0),
+                (ProofPhase::Root, 0),
+            ]
+        );
+        assert_eq!(analysis.execution_time(), Some(Duration::from_secs(9)));
+        assert_eq!(analysis.proving_time(), Some(Duration::from_secs(115)));
+        assert_eq!(analysis.segments.len(), 2);
+        assert_eq!(analysis.segments[1].item, 1);
+        assert_eq!(
+            analysis.peak_memory,
+            Some((41.5 * (1u64 << 30) as f64) as u64)
+        );
+
+        assert_eq!(
+            span_field_values(
+                "x{group=leaf}:y{group=\"internal.1\" idx=2}: segment{a=1} b",
+                "group"
+            ),
+            vec!["leaf", "internal.1"]
+        );
+        assert!(!in_execution_span("INFO openvm_sdk::execute_metered: done"));
+    }
+
+    #[test]
+    fn test_diagnose_failure() {
+        assert_eq!(
+            diagnose_failure(Some("Container OOMKilled"), ""),
+            Some(FailureCause::OutOfMemory)
+        );
+        assert_eq!(
+            diagnose_failure(
+                Some("Proof failed"),
+                "memory allocation of 34359738368 bytes failed"
+            ),
+            Some(FailureCause::OutOfMemory)
+        );
+        let guest_panic = "\
+panicked at src/main.rs:10:5:
+index out of bounds: the len is 3 but the index is 3
+2025-06-01T12:00:03.000000Z ERROR openvm_sdk::prover: execution failed: program exit code 1
+";
+        assert_eq!(
+            diagnose_failure(Some("Proof failed"), guest_panic),
+            Some(FailureCause::GuestPanic)
+        );
+        assert_eq!(
+            diagnose_failure(Some("Execution error: program must terminate"), ""),
+            Some(FailureCause::CycleLimit)
+        );
+        assert_eq!(
+            diagnose_failure(
+                None,
+                "Error: at pc 2097340, discriminant 0, phantom error: EndOfInputStream"
+            ),
+            Some(FailureCause::InvalidInput)
+        );
+        // A panic of the prover itself is not a guest panic
+        assert_eq!(
+            diagnose_failure(
+                Some("internal error"),
+                "thread 'main' panicked at src/main.rs:10:5: trace height too large"
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,457 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use eyre::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AxiomSdk, NoopCallback, ProgressCallback, ProofType, format_duration,
+    input::Input,
+    poll_sleep,
+    prove::{ProveArgs, ProveSdk},
+    start_wait,
+    timeline::Timeline,
+};
+
+const BATCH_POLLING_INTERVAL_SECS: u64 = 10;
+/// Attempts at downloading a successful proof before recording it as unsaved
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 3;
+/// Entry state of a proof that succeeded but could not be downloaded
+pub const UNSAVED_STATE: &str = "Unsaved";
+pub const BATCH_STATE_FILE_NAME: &str = "batch-state.json";
+
+pub trait BatchSdk {
+    /// Prove a program over every input in a directory of JSON files or a JSONL file,
+    /// keeping at most `concurrency` proofs in flight.
+    fn prove_batch(&self, args: BatchProveArgs) -> Result<BatchSummary>;
+}
+
+#[derive(Debug)]
+pub struct BatchProveArgs {
+    pub program_id: String,
+    /// Directory of `*.json` input files, or a `.jsonl` file with one input per line
+    pub inputs: PathBuf,
+    pub proof_type: ProofType,
+    pub num_gpus: Option<usize>,
+    pub priority: Option<u8>,
+    /// Maximum number of proofs submitted but not yet finished
+    pub concurrency: usize,
+    /// Where to write results and the state file. Defaults to
+    /// `axiom-artifacts/program-<id>/batches/<inputs name>`.
+    pub output_dir: Option<PathBuf>,
+}
+
+/// One input of a batch, named after its file or JSONL line
+#[derive(Debug, Clone)]
+pub struct BatchInput {
+    pub name: String,
+    pub input: Input,
+}
+
+/// Progress of a batch, persisted after every change so that a re-run can resume it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchState {
+    pub program_id: String,
+    pub proof_type: String,
+    pub entries: BTreeMap<String, BatchEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchEntry {
+    pub proof_id: Option<String>,
+    /// Last known proof state, e.g. "Queued" or "Succeeded", or [`UNSAVED_STATE`]
+    pub state: String,
+    pub cells_used: Option<u64>,
+    pub duration: Option<String>,
+    pub error: Option<String>,
+    /// Where the proof was saved, if it succeeded
+    pub proof_path: Option<PathBuf>,
+}
+
+impl BatchEntry {
+    /// Whether the proof succeeded and was saved
+    pub fn is_done(&self) -> bool {
+        self.is_succeeded() && self.proof_path.as_ref().is_some_and(|path| path.exists())
+    }
+
+    fn is_succeeded(&self) -> bool {
+        self.state == "Succeeded"
+    }
+
+    fn is_unsaved(&self) -> bool {
+        self.state == UNSAVED_STATE
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.state.as_str(), "Succeeded" | "Failed" | "Canceled")
+    }
+}
+
+#[derive(Debug)]
+pub struct BatchSummary {
+    pub output_dir: PathBuf,
+    pub state: BatchState,
+}
+
+impl BatchSummary {
+    pub fn count(&self, state: &str) -> usize {
+        self.state
+            .entries
+            .values()
+            .filter(|entry| entry.state == state)
+            .count()
+    }
+
+    pub fn total_cells_used(&self) -> u64 {
+        self.state
+            .entries
+            .values()
+            .filter_map(|entry| entry.cells_used)
+            .sum()
+    }
+}
+
+impl BatchState {
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+            .map(Some)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        // Write to a temporary file first so an interrupted run never leaves a truncated state
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Load the inputs of a batch from a directory of `*.json` files (named after the file
+/// stem) or a JSONL file (named `line-<n>`, or after a `"name"` key in the line).
+pub fn load_batch_inputs(path: &Path) -> Result<Vec<BatchInput>> {
+    if path.is_dir() {
+        let mut files = std::fs::read_dir(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect::<Vec<_>>();
+        files.sort();
+        return Ok(files
+            .into_iter()
+            .map(|file| BatchInput {
+                name: file
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                input: Input::FilePath(file),
+            })
+            .collect());
+    }
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_jsonl_inputs(&content)
+}
+
+fn parse_jsonl_inputs(content: &str) -> Result<Vec<BatchInput>> {
+    let mut inputs = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut value: serde_json::Value = serde_json::from_str(line)
+            .with_context(|| format!("Line {} is not valid JSON", i + 1))?;
+        let name = match value.as_object_mut().and_then(|obj| obj.remove("name")) {
+            Some(serde_json::Value::String(name)) => {
+                // Names become directory names in the output
+                if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+                    eyre::bail!("Line {}: invalid name '{}'", i + 1, name);
+                }
+                name
+            }
+            Some(_) => eyre::bail!("Line {}: 'name' must be a string", i + 1),
+            None => format!("line-{}", i + 1),
+        };
+        inputs.push(BatchInput {
+            name,
+            input: Input::Value(value),
+        });
+    }
+
+    let mut names = std::collections::HashSet::new();
+    if let Some(duplicate) = inputs.iter().find(|input| !names.insert(&input.name)) {
+        eyre::bail!("Duplicate input name '{}'", duplicate.name);
+    }
+    Ok(inputs)
+}
+
+impl BatchSdk for AxiomSdk {
+    fn prove_batch(&self, args: BatchProveArgs) -> Result<BatchSummary> {
+        self.prove_batch_base(args, &*self.callback)
+    }
+}
+
+impl AxiomSdk {
+    pub fn prove_batch_base(
+        &self,
+        args: BatchProveArgs,
+        callback: &dyn ProgressCallback,
+    ) -> Result<BatchSummary> {
+        let _wait = start_wait();
+        if args.concurrency == 0 {
+            eyre::bail!("Concurrency must be at least 1");
+        }
+        let inputs = load_batch_inputs(&args.inputs)?;
+        if inputs.is_empty() {
+            eyre::bail!("No inputs found in {}", args.inputs.display());
+        }
+
+        let output_dir = match args.output_dir {
+            Some(dir) => dir,
+            None => PathBuf::from("axiom-artifacts")
+                .join(format!("program-{}", args.program_id))
+                .join("batches")
+                .join(
+                    args.inputs
+                        .file_stem()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .as_ref(),
+                ),
+        };
+        std::fs::create_dir_all(&output_dir)
+            .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+        let state_path = output_dir.join(BATCH_STATE_FILE_NAME);
+
+        let proof_type = args.proof_type.to_string();
+        let mut state = match BatchState::load(&state_path)? {
+            Some(state) => {
+                if state.program_id != args.program_id || state.proof_type != proof_type {
+                    eyre::bail!(
+                        "{} belongs to a batch of {} proofs for program {}. \
+                        Use a different --output-dir for this batch.",
+                        state_path.display(),
+                        state.proof_type,
+                        state.program_id
+                    );
+                }
+                state
+            }
+            None => BatchState {
+                program_id: args.program_id.clone(),
+                proof_type: proof_type.clone(),
+                entries: BTreeMap::new(),
+            },
+        };
+
+        callback.on_header("Proving Batch");
+        callback.on_field("Program ID", &args.program_id);
+        callback.on_field("Proof Type", &proof_type.to_uppercase());
+        callback.on_field("Inputs", &inputs.len().to_string());
+        callback.on_field("Output", &output_dir.display().to_string());
+
+        // Proofs submitted by an earlier run are picked up again, including unsaved
+        // successes, which are only downloaded again; other finished ones are submitted again.
+        let mut in_flight = Vec::new();
+        let mut pending = VecDeque::new();
+        for input in &inputs {
+            match state.entries.get(&input.name) {
+                Some(entry) if entry.is_done() => {}
+                Some(entry)
+                    if (!entry.is_finished() || entry.is_succeeded() || entry.is_unsaved())
+                        && entry.proof_id.is_some() =>
+                {
+                    in_flight.push(input.name.clone());
+                }
+                _ => pending.push_back(input.clone()),
+            }
+        }
+        let already_done = inputs.len() - in_flight.len() - pending.len();
+        if already_done > 0 {
+            callback.on_info(&format!(
+                "Skipping {already_done} inputs that were already proven"
+            ));
+        }
+
+        let total = inputs.len();
+        let mut finished = already_done;
+        let mut download_attempts: HashMap<String, u32> = HashMap::new();
+        while !pending.is_empty() || !in_flight.is_empty() {
+            while in_flight.len() < args.concurrency
+                && let Some(input) = pending.pop_front()
+            {
+                let prove_args = ProveArgs {
+                    program_id: Some(args.program_id.clone()),
+                    input: Some(input.input.clone()),
+                    proof_type: Some(args.proof_type),
+                    num_gpus: args.num_gpus,
+                    priority: args.priority,
+                    deferred_proofs: Vec::new(),
+                };
+                let entry = match self.generate_new_proof_base(prove_args, &NoopCallback) {
+                    Ok(proof_id) => {
+                        callback.on_info(&format!("{}: submitted proof {}", input.name, proof_id));
+                        in_flight.push(input.name.clone());
+                        BatchEntry {
+                            proof_id: Some(proof_id),
+                            state: "Queued".to_string(),
+                            ..Default::default()
+                        }
+                    }
+                    Err(e) => {
+                        finished += 1;
+                        callback.on_error(&format!(
+                            "[{finished}/{total}] {}: failed to submit: {e}",
+                            input.name
+                        ));
+                        BatchEntry {
+                            state: "Failed".to_string(),
+                            error: Some(e.to_string()),
+                            ..Default::default()
+                        }
+                    }
+                };
+                state.entries.insert(input.name.clone(), entry);
+                state.save(&state_path)?;
+            }
+
+            poll_sleep(Duration::from_secs(BATCH_POLLING_INTERVAL_SECS), callback)?;
+
+            let mut still_in_flight = Vec::new();
+            for name in in_flight {
+                let entry = state.entries.get_mut(&name).expect("in-flight entry");
+                let proof_id = entry.proof_id.clone().expect("in-flight proof id");
+                if !entry.is_unsaved() {
+                    let status = match self.get_proof_status(&proof_id) {
+                        Ok(status) => status,
+                        Err(e) => {
+                            callback.on_warning(&format!("{name}: failed to get status: {e}"));
+                            still_in_flight.push(name);
+                            continue;
+                        }
+                    };
+
+                    entry.state = status.state.clone();
+                    if !entry.is_finished() {
+                        still_in_flight.push(name);
+                        continue;
+                    }
+
+                    entry.cells_used = Some(status.cells_used);
+                    entry.duration = Timeline::from(&status)
+                        .finished_run_time()
+                        .map(|duration| format_duration(duration.as_secs() as i64));
+                    entry.error = status.error_message.clone();
+
+                    if !entry.is_succeeded() {
+                        finished += 1;
+                        callback.on_error(&format!(
+                            "[{finished}/{total}] {name}: {}{}",
+                            entry.state,
+                            entry
+                                .error
+                                .as_ref()
+                                .map(|e| format!(" ({e})"))
+                                .unwrap_or_default()
+                        ));
+                        state.save(&state_path)?;
+                        continue;
+                    }
+                }
+
+                let proof_path = output_dir
+                    .join(&name)
+                    .join(format!("{}-proof.json", proof_type));
+                std::fs::create_dir_all(proof_path.parent().unwrap())?;
+                match self.get_generated_proof(
+                    &proof_id,
+                    &args.proof_type,
+                    Some(proof_path.clone()),
+                ) {
+                    Ok(_) => {
+                        finished += 1;
+                        entry.state = "Succeeded".to_string();
+                        entry.error = None;
+                        entry.proof_path = Some(proof_path);
+                        callback.on_success(&format!("[{finished}/{total}] {name}: Succeeded"));
+                    }
+                    Err(e) => {
+                        // Retried on the next poll, then left for the next run
+                        entry.state = UNSAVED_STATE.to_string();
+                        entry.error = Some(format!("Failed to save proof: {e}"));
+                        let attempts = download_attempts.entry(name.clone()).or_default();
+                        *attempts += 1;
+                        if *attempts < MAX_DOWNLOAD_ATTEMPTS {
+                            callback.on_warning(&format!(
+                                "{name}: failed to save proof (attempt {attempts}/{MAX_DOWNLOAD_ATTEMPTS}): {e}"
+                            ));
+                            still_in_flight.push(name);
+                        } else {
+                            finished += 1;
+                            callback.on_error(&format!(
+                                "[{finished}/{total}] {name}: Succeeded, but the proof could not be saved: {e}"
+                            ));
+                        }
+                    }
+                }
+                state.save(&state_path)?;
+            }
+            in_flight = still_in_flight;
+        }
+
+        Ok(BatchSummary { output_dir, state })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jsonl_inputs() {
+        let content = r#"{"input": ["0x01aa"]}
+
+{"name": "big", "input": ["0x01bb"]}
+"#;
+        let inputs = parse_jsonl_inputs(content).unwrap();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[0].name, "line-1");
+        assert_eq!(inputs[1].name, "big");
+        // The name is not part of the submitted input
+        let Input::Value(value) = &inputs[1].input else {
+            panic!("expected a JSON value");
+        };
+        assert_eq!(value, &serde_json::json!({ "input": ["0x01bb"] }));
+
+        let duplicate = r#"{"name": "a", "input": []}
+{"name": "a", "input": []}"#;
+        assert!(parse_jsonl_inputs(duplicate).is_err());
+    }
+
+    #[test]
+    fn test_batch_entry_states() {
+        let entry = |state: &str| BatchEntry {
+            state: state.to_string(),
+            ..Default::default()
+        };
+        assert!(entry("Succeeded").is_succeeded());
+        // A success whose download failed is downloaded again on resume
+        assert!(!entry("Succeeded").is_done());
+        assert!(entry(UNSAVED_STATE).is_unsaved());
+        assert!(!entry(UNSAVED_STATE).is_succeeded());
+        assert!(!entry(UNSAVED_STATE).is_done());
+        assert!(entry("Failed").is_finished());
+        assert!(!entry("Failed").is_done());
+        assert!(!entry("InProgress").is_finished());
+    }
+}
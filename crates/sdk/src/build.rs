@@ -1,8 +1,9 @@
 use std::{
     borrow::Cow,
+    collections::HashSet,
     fs::File,
-    io::Read,
-    path::Path,
+    io::{Read, Write},
+    path::{Path, PathBuf},
     sync::{
         Arc,
         atomic::{AtomicU64, Ordering},
@@ -10,22 +11,36 @@ use std::{
 };
 
 use eyre::{Context, OptionExt, Result, eyre};
-use flate2::{Compression, write::GzEncoder};
+use flate2::{Compression, GzBuilder};
 use reqwest::blocking::Client;
 use scopeguard::defer;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tar::Builder;
+use sha2::{Digest, Sha256};
+use tar::{Builder, HeaderMode};
 
 use crate::{
     API_KEY_HEADER, AxiomSdk, CountingReader, ProgressCallback, add_cli_version_header,
-    authenticated_get, send_request_json,
+    authenticated_delete, authenticated_get, authenticated_put, download_file, get_axiom_dir,
+    poll_sleep,
+    secrets::{SECRETS_IGNORE_FILE, read_secrets_allowlist, scan_file_names, scan_files},
+    send_request, send_request_json, sha256_file, start_wait,
 };
 
 pub const MAX_PROGRAM_SIZE_MB: u64 = 2048;
 const BUILD_POLLING_INTERVAL_SECS: u64 = 10;
+const LOGS_POLLING_INTERVAL_SECS: u64 = 5;
+/// Number of compiler errors from the logs to show when a build fails
+const MAX_REPORTED_COMPILER_ERRORS: usize = 3;
 
 pub const AXIOM_CARGO_HOME: &str = "axiom_cargo_home";
+/// Directory under `~/.axiom` holding pre-fetched cargo homes
+const FETCH_CACHE_DIR: &str = "cargo-cache";
+/// Written into a cached cargo home once all fetches have succeeded
+const FETCH_CACHE_COMPLETE_MARKER: &str = ".axiom-fetch-complete";
+/// Files from the user's cargo home that are used for fetching but never archived
+const CARGO_HOME_CONFIG_FILES: [&str; 4] =
+    ["config.toml", "config", "credentials.toml", "credentials"];
 
 // Mirrors of constants/helpers from `openvm-build`. Keep in sync with the openvm
 // version pinned by the proving API (currently v1.6.0). These let the SDK avoid
@@ -102,12 +117,34 @@ pub trait BuildSdk {
         page: Option<u32>,
         page_size: Option<u32>,
     ) -> Result<ProgramListResponse>;
+    /// List programs matching the filters and ordering in `query`
+    fn list_programs_with_query(
+        &self,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        query: &ProgramListQuery,
+    ) -> Result<ProgramListResponse>;
     fn get_build_status(&self, program_id: &str) -> Result<BuildStatus>;
 
     /// Get the app EXE commitment hash for a program
     fn get_app_exe_commit(&self, program_id: &str) -> Result<Vec<u8>>;
 
     fn download_program(&self, program_id: &str, program_type: &str) -> Result<()>;
+
+    /// Download the build logs of a program to its artifacts directory
+    fn get_build_logs(&self, program_id: &str) -> Result<()>;
+    fn save_build_logs_to_path(&self, program_id: &str, output_path: PathBuf) -> Result<()>;
+    /// Print the build logs as they are written until the build finishes, then save them
+    fn follow_build_logs(&self, program_id: &str) -> Result<()>;
+
+    /// Update a program's name, default GPU count, labels or archived state
+    fn update_program(&self, program_id: &str, update: ProgramUpdate) -> Result<()>;
+    /// Archive a program, hiding it from program listings
+    fn archive_program(&self, program_id: &str) -> Result<()>;
+    fn unarchive_program(&self, program_id: &str) -> Result<()>;
+    /// Permanently delete a program
+    fn delete_program(&self, program_id: &str) -> Result<()>;
+
     fn register_new_program(
         &self,
         program_dir: impl AsRef<Path>,
@@ -144,6 +181,49 @@ pub struct BuildStatus {
     pub project_id: String,
     pub project_name: String,
     pub default_num_gpus: usize,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// Changes to a program's metadata. Fields left as `None` are not changed.
+#[derive(Debug, Default, Serialize)]
+pub struct ProgramUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_num_gpus: Option<usize>,
+    /// Replaces the program's labels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+/// Filters and ordering for program listings. Unset fields are not sent.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ProgramListQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    /// RFC3339 timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<String>,
+    /// RFC3339 timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Substring of the program name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Field to sort by, prefixed with `-` for descending order (e.g. "-created_at")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub include_archived: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -182,6 +262,15 @@ pub struct BuildArgs {
     pub default_num_gpus: Option<usize>,
     /// OpenVM Rust toolchain version (e.g., "nightly-2025-02-14")
     pub openvm_rust_toolchain: Option<String>,
+    /// Build from this git revision (commit, tag or branch) instead of the working tree
+    pub rev: Option<String>,
+    /// Upload the archive even if it looks like it contains secrets
+    pub allow_secrets: bool,
+    /// `cargo vendor` directory, relative to the cargo workspace root, to take dependencies
+    /// from instead of fetching them
+    pub vendor_dir: Option<String>,
+    /// Fetch dependencies again even if they are cached
+    pub refresh_fetch_cache: bool,
 }
 
 #[derive(Debug)]
@@ -210,6 +299,8 @@ pub enum ConfigSource {
 struct TarFile {
     path: String,
     keep: bool,
+    /// Hex-encoded SHA-256 of the archive
+    sha256: String,
 }
 
 impl Drop for TarFile {
@@ -220,11 +311,40 @@ impl Drop for TarFile {
     }
 }
 
+/// The files that go into a source archive.
+struct ArchiveSource {
+    /// Directory the archive is built from: the git root, or a revision exported from it
+    root: PathBuf,
+    /// Name of the top-level directory inside the archive
+    prefix: String,
+    /// Paths relative to `root` that may be archived
+    files: HashSet<String>,
+    /// Whether `root` is a scratch export that should be removed once we're done
+    scratch: bool,
+}
+
+impl Drop for ArchiveSource {
+    fn drop(&mut self) {
+        if self.scratch {
+            std::fs::remove_dir_all(&self.root).ok();
+        }
+    }
+}
+
 impl BuildSdk for AxiomSdk {
     fn list_programs(
         &self,
         page: Option<u32>,
         page_size: Option<u32>,
+    ) -> Result<ProgramListResponse> {
+        self.list_programs_with_query(page, page_size, &ProgramListQuery::default())
+    }
+
+    fn list_programs_with_query(
+        &self,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        query: &ProgramListQuery,
     ) -> Result<ProgramListResponse> {
         let page = page.unwrap_or(1);
         let page_size = page_size.unwrap_or(20);
@@ -233,7 +353,7 @@ impl BuildSdk for AxiomSdk {
             self.config.api_url, page, page_size
         );
 
-        let request = authenticated_get(&self.config, &url)?;
+        let request = authenticated_get(&self.config, &url)?.query(query);
         send_request_json(request, "Failed to list programs")
     }
 
@@ -258,6 +378,58 @@ impl BuildSdk for AxiomSdk {
         hex::decode(app_exe_commit.trim()).context("Failed to decode app_exe_commit hex string")
     }
 
+    fn get_build_logs(&self, program_id: &str) -> Result<()> {
+        let output_path = build_logs_path(program_id);
+        self.save_build_logs_to_path(program_id, output_path.clone())?;
+        self.callback
+            .on_success(&format!("{}", output_path.display()));
+        Ok(())
+    }
+
+    fn save_build_logs_to_path(&self, program_id: &str, output_path: PathBuf) -> Result<()> {
+        let url = format!("{}/programs/{}/logs", self.config.api_url, program_id);
+        let request = authenticated_get(&self.config, &url)?;
+        crate::download_file_streaming(request, output_path, "Failed to download build logs")
+    }
+
+    fn follow_build_logs(&self, program_id: &str) -> Result<()> {
+        self.follow_build_logs_base(program_id, &*self.callback)
+    }
+
+    fn update_program(&self, program_id: &str, update: ProgramUpdate) -> Result<()> {
+        let url = format!("{}/programs/{}", self.config.api_url, program_id);
+        let request = authenticated_put(&self.config, &url)?
+            .header("Content-Type", "application/json")
+            .json(&update);
+        send_request(request, "Failed to update program")
+    }
+
+    fn archive_program(&self, program_id: &str) -> Result<()> {
+        self.update_program(
+            program_id,
+            ProgramUpdate {
+                archived: Some(true),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn unarchive_program(&self, program_id: &str) -> Result<()> {
+        self.update_program(
+            program_id,
+            ProgramUpdate {
+                archived: Some(false),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn delete_program(&self, program_id: &str) -> Result<()> {
+        let url = format!("{}/programs/{}", self.config.api_url, program_id);
+        let request = authenticated_delete(&self.config, &url)?;
+        send_request(request, "Failed to delete program")
+    }
+
     fn download_program(&self, program_id: &str, program_type: &str) -> Result<()> {
         // Handle "all" artifact type by downloading each type sequentially
         if program_type == "all" {
@@ -284,76 +456,26 @@ impl BuildSdk for AxiomSdk {
             .build()?;
         let api_key = self.config.api_key.as_ref().ok_or_eyre("API key not set")?;
 
-        let response = add_cli_version_header(client.get(url).header(API_KEY_HEADER, api_key))
-            .send()
-            .context("Failed to download artifact")?;
-
-        let status = response.status();
-
-        if status.is_success() {
-            // Create organized directory structure
-            let build_dir = std::path::PathBuf::from("axiom-artifacts")
-                .join(format!("program-{}", program_id))
-                .join("artifacts");
-            std::fs::create_dir_all(&build_dir).context(format!(
-                "Failed to create build directory: {}",
-                build_dir.display()
-            ))?;
-
-            // Create output filename based on artifact type
-            let ext = if program_type == "source" {
-                "tar.gz"
-            } else {
-                program_type
-            };
-            let filename = build_dir.join(format!("program.{}", ext));
-
-            // Write the response body to a file using streaming
-            let mut file = File::create(&filename).context(format!(
-                "Failed to create output file: {}",
-                filename.display()
-            ))?;
-
-            let content_length = response.content_length();
-            let mut response = response;
-
-            self.callback.on_progress_start(
-                &format!("Downloading {program_type}"),
-                content_length,
-                crate::TransferDirection::Download,
-            );
-
-            crate::stream_response_to_file(
-                &mut response,
-                &mut file,
-                &*self.callback,
-                content_length.is_some(),
-            )?;
-
-            self.callback.on_progress_finish("✓ Download complete");
-            self.callback.on_success(&format!("{}", filename.display()));
-            Ok(())
-        } else if status.is_client_error() {
-            let error_text = response
-                .text()
-                .unwrap_or_else(|_| "Unable to read error response".to_string());
-            self.callback.on_progress_finish("");
-            self.callback
-                .on_error(&format!("Client error response: {}", error_text));
-            Err(eyre::eyre!("Client error ({}): {}", status, error_text))
+        // Create output filename based on artifact type
+        let ext = if program_type == "source" {
+            "tar.gz"
         } else {
-            self.callback.on_progress_finish("");
-            let error_text = response
-                .text()
-                .unwrap_or_else(|_| "Unable to read error response".to_string());
-            self.callback
-                .on_error(&format!("Server error response: {}", error_text));
-            Err(eyre::eyre!(
-                "Download request failed with status: {} - {}",
-                status,
-                error_text
-            ))
-        }
+            program_type
+        };
+        let filename = std::path::PathBuf::from("axiom-artifacts")
+            .join(format!("program-{}", program_id))
+            .join("artifacts")
+            .join(format!("program.{}", ext));
+
+        crate::download_resumable(
+            add_cli_version_header(client.get(url).header(API_KEY_HEADER, api_key)),
+            &filename,
+            Some(&format!("Downloading {program_type}")),
+            &*self.callback,
+            "Failed to download artifact",
+        )?;
+        self.callback.on_success(&format!("{}", filename.display()));
+        Ok(())
     }
 
     fn register_new_program(
@@ -383,11 +505,55 @@ impl BuildSdk for AxiomSdk {
 }
 
 impl AxiomSdk {
+    pub fn follow_build_logs_base(
+        &self,
+        program_id: &str,
+        callback: &dyn ProgressCallback,
+    ) -> Result<()> {
+        let _wait = start_wait();
+        use std::time::Duration;
+
+        let url = format!("{}/programs/{}/logs", self.config.api_url, program_id);
+        let mut printed = 0;
+        let mut stdout = std::io::stdout();
+        loop {
+            // Check the status first so that the logs fetched after it are complete
+            let build_status = self.get_build_status(program_id)?;
+            let in_progress = matches!(build_status.status.as_str(), "processing" | "not_ready");
+
+            let request = authenticated_get(&self.config, &url)?;
+            let logs = download_file(request, None, "Failed to download build logs")?;
+            // The logs start over if the build was restarted
+            if logs.len() < printed {
+                printed = 0;
+            }
+            stdout.write_all(&logs[printed..])?;
+            stdout.flush()?;
+            printed = logs.len();
+
+            if !in_progress {
+                let output_path = build_logs_path(program_id);
+                if let Some(parent) = output_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&output_path, &logs)?;
+                callback.on_success(&format!(
+                    "Build {}, logs saved to {}",
+                    build_status.status,
+                    output_path.display()
+                ));
+                return Ok(());
+            }
+            poll_sleep(Duration::from_secs(LOGS_POLLING_INTERVAL_SECS), callback)?;
+        }
+    }
+
     pub fn wait_for_build_completion_base(
         &self,
         program_id: &str,
         callback: &dyn ProgressCallback,
     ) -> Result<()> {
+        let _wait = start_wait();
         use std::time::Duration;
 
         callback.on_spinner_start("Checking build status...");
@@ -423,6 +589,9 @@ impl AxiomSdk {
                         "Default Num GPUs",
                         &build_status.default_num_gpus.to_string(),
                     );
+                    if !build_status.labels.is_empty() {
+                        callback.on_field("Labels", &build_status.labels.join(", "));
+                    }
 
                     if let Some(launched_at) = &build_status.launched_at {
                         callback.on_field("Launched At", launched_at);
@@ -447,10 +616,25 @@ impl AxiomSdk {
                         &build_status.id
                     ));
 
+                    self.hooks.run(
+                        "build",
+                        program_id,
+                        &build_status.status,
+                        &build_status,
+                        callback,
+                    );
                     return Ok(());
                 }
                 "error" | "failed" => {
                     callback.on_progress_finish("");
+                    self.report_build_failure(program_id, callback);
+                    self.hooks.run(
+                        "build",
+                        program_id,
+                        &build_status.status,
+                        &build_status,
+                        callback,
+                    );
                     let error_msg = build_status
                         .error_message
                         .unwrap_or_else(|| "Unknown error".to_string());
@@ -458,23 +642,45 @@ impl AxiomSdk {
                 }
                 "processing" => {
                     callback.on_progress_update_message("Building program");
-                    std::thread::sleep(Duration::from_secs(BUILD_POLLING_INTERVAL_SECS));
+                    poll_sleep(Duration::from_secs(BUILD_POLLING_INTERVAL_SECS), callback)?;
                 }
                 "not_ready" => {
                     callback.on_progress_update_message("Build queued");
-                    std::thread::sleep(Duration::from_secs(BUILD_POLLING_INTERVAL_SECS));
+                    poll_sleep(Duration::from_secs(BUILD_POLLING_INTERVAL_SECS), callback)?;
                 }
                 _ => {
                     callback.on_progress_update_message(&format!(
                         "Build status: {}",
                         build_status.status
                     ));
-                    std::thread::sleep(Duration::from_secs(BUILD_POLLING_INTERVAL_SECS));
+                    poll_sleep(Duration::from_secs(BUILD_POLLING_INTERVAL_SECS), callback)?;
                 }
             }
         }
     }
 
+    /// Save the logs of a failed build and print the last compiler errors in them.
+    /// Problems fetching the logs are only warned about.
+    fn report_build_failure(&self, program_id: &str, callback: &dyn ProgressCallback) {
+        let logs_path = build_logs_path(program_id);
+        if let Err(e) = self.save_build_logs_to_path(program_id, logs_path.clone()) {
+            callback.on_warning(&format!("Failed to save build logs: {}", e));
+            return;
+        }
+        callback.on_info(&format!("Build logs saved to {}", logs_path.display()));
+
+        let Ok(logs) = std::fs::read_to_string(&logs_path) else {
+            return;
+        };
+        let errors = last_compiler_errors(&logs, MAX_REPORTED_COMPILER_ERRORS);
+        if !errors.is_empty() {
+            callback.on_section("Compiler Errors");
+            for error in errors {
+                callback.on_error(&error);
+            }
+        }
+    }
+
     pub fn register_new_program_base(
         &self,
         program_dir: impl AsRef<Path>,
@@ -490,16 +696,85 @@ impl AxiomSdk {
             "Not in a git repository. Please run this command from within a git repository.",
         )?;
 
-        // Check if git repository is clean unless allow-dirty is specified
-        if !args.allow_dirty {
-            let is_clean = check_git_clean(&git_root)?;
-            if !is_clean {
+        // Parse exclude patterns
+        let exclude_patterns = args
+            .exclude_files
+            .map(|patterns| {
+                patterns
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default();
+
+        // Parse include directories
+        let include_dirs = args
+            .include_dirs
+            .map(|dirs| {
+                dirs.split(',')
+                    .map(|s| s.trim().to_string())
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default();
+
+        let archive_prefix = git_root
+            .file_name()
+            .ok_or_eyre("Failed to get git root directory name")?
+            .to_string_lossy()
+            .to_string();
+
+        // Either archive the git-tracked files of the working tree, or export the tree of
+        // the requested revision into a scratch directory and archive that instead.
+        let (source, commit_sha) = if let Some(rev) = &args.rev {
+            if !include_dirs.is_empty() {
+                eyre::bail!("--include-dirs cannot be used together with --rev");
+            }
+            // A vendor directory is usually untracked, so it is not in the exported tree
+            if args.vendor_dir.is_some() {
                 eyre::bail!(
-                    "Git repository has uncommitted changes. Please commit your changes or use --allow-dirty to build anyway.\n\
-                    Run 'git status' to see uncommitted changes."
+                    "--vendor-dir cannot be used together with --rev, because the vendor directory is not part of the exported revision"
                 );
             }
-        }
+            let sha = resolve_git_rev(&git_root, rev)?;
+            callback.on_info(&format!("Exporting {rev} ({sha})..."));
+            let export_dir =
+                std::env::temp_dir().join(format!("axiom-rev-{}-{}", sha, std::process::id()));
+            export_git_rev(&git_root, &sha, &export_dir)?;
+            let files = list_files(&export_dir)?;
+            let source = ArchiveSource {
+                root: export_dir,
+                prefix: archive_prefix,
+                files,
+                scratch: true,
+            };
+            (source, Some(sha))
+        } else {
+            let files = git_tracked_files(&git_root)?;
+
+            // Check that none of the files going into the archive have uncommitted changes,
+            // unless allow-dirty is specified
+            if !args.allow_dirty {
+                let dirty = dirty_tracked_files(&git_root)?
+                    .into_iter()
+                    .filter(|path| !is_excluded(path, &exclude_patterns))
+                    .collect::<Vec<_>>();
+                if !dirty.is_empty() {
+                    eyre::bail!(
+                        "Files to be archived have uncommitted changes:\n  {}\n\
+                        Please commit your changes or use --allow-dirty to build anyway.",
+                        dirty.join("\n  ")
+                    );
+                }
+            }
+
+            let source = ArchiveSource {
+                root: git_root.clone(),
+                prefix: archive_prefix,
+                files,
+                scratch: false,
+            };
+            (source, get_git_commit_sha(&git_root).ok())
+        };
 
         let config_id = match &args.config_source {
             Some(ConfigSource::ConfigId(id)) => Some(id.clone()),
@@ -526,8 +801,14 @@ impl AxiomSdk {
             .to_string_lossy()
             .to_string();
 
-        // Check for bin flag
-        let current_dir = program_dir.as_ref().to_path_buf();
+        // Check for bin flag, looking at the program as it will be archived
+        let current_dir = source.root.join(&program_path);
+        if !is_rust_project(&current_dir) {
+            eyre::bail!(
+                "Program directory '{}' does not contain a Cargo.toml in the archived tree",
+                program_path
+            );
+        }
         let metadata = cargo_metadata::MetadataCommand::new()
             .current_dir(current_dir.clone())
             .exec()?;
@@ -597,35 +878,67 @@ impl AxiomSdk {
             None
         };
 
-        // Parse exclude patterns
-        let exclude_patterns = args
-            .exclude_files
-            .map(|patterns| {
-                patterns
-                    .split(',')
-                    .map(|s| s.trim().to_string())
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_default();
+        // A vendor directory goes into the archive even if it isn't tracked by git
+        let vendor_path = args.vendor_dir.as_ref().map(|vendor_dir| {
+            Path::new(&cargo_root_path)
+                .join(vendor_dir)
+                .components()
+                .filter(|component| !matches!(component, std::path::Component::CurDir))
+                .collect::<PathBuf>()
+        });
+        let mut include_dirs = include_dirs;
+        if let Some(vendor_path) = &vendor_path {
+            include_dirs.push(vendor_path.to_string_lossy().to_string());
+        }
+        let files = collect_archive_files(&source, &exclude_patterns, &include_dirs)?;
 
-        // Parse include directories
-        let include_dirs = args
-            .include_dirs
-            .map(|dirs| {
-                dirs.split(',')
-                    .map(|s| s.trim().to_string())
-                    .collect::<Vec<String>>()
-            })
-            .unwrap_or_default();
+        // Refuse to upload anything that looks like a secret, unless allowlisted. Vendored
+        // crates are full of test keys and fixtures, so only their file names are checked.
+        let allowlist = read_secrets_allowlist(&source.root)?;
+        let (vendored, own): (Vec<_>, Vec<_>) = files.iter().cloned().partition(|path| {
+            vendor_path
+                .as_ref()
+                .is_some_and(|vendor_path| path.starts_with(vendor_path))
+        });
+        let mut findings = scan_files(&source.root, &own, &allowlist);
+        findings.extend(scan_file_names(&vendored, &allowlist));
+        if !findings.is_empty() {
+            let list = findings
+                .iter()
+                .map(|f| f.to_string())
+                .collect::<Vec<_>>()
+                .join("\n  ");
+            if !args.allow_secrets {
+                eyre::bail!(
+                    "Files to be archived look like they contain secrets:\n  {list}\n\
+                    Exclude them with --exclude-files, allowlist them in {SECRETS_IGNORE_FILE}, \
+                    or use --allow-secrets to upload anyway."
+                );
+            }
+            callback.on_warning(&format!(
+                "Uploading files that look like they contain secrets:\n  {list}"
+            ));
+        }
+
+        // Pre-fetch dependencies so private dependencies are included in the archive
+        let cargo_home = prefetch_dependencies(
+            &source.root.join(&cargo_root_path),
+            args.vendor_dir.as_deref(),
+            args.refresh_fetch_cache,
+            args.openvm_rust_toolchain.as_deref(),
+            callback,
+        )?;
 
         // Create tar archive of the current directory
         callback.on_info("Creating project archive...");
         let tar_file = create_tar_archive(
             program_dir.as_ref(),
+            &source,
+            &cargo_root_path,
+            &cargo_home,
+            &files,
             args.keep_tarball.unwrap_or(false),
             &exclude_patterns,
-            &include_dirs,
-            args.openvm_rust_toolchain.clone(),
         )?;
         let tar_path = &tar_file.path;
 
@@ -668,7 +981,7 @@ impl AxiomSdk {
         if let Some(bin) = bin_to_build {
             url.push_str(&format!("&bin_name={bin}"));
         }
-        if let Ok(sha) = get_git_commit_sha(&git_root) {
+        if let Some(sha) = &commit_sha {
             url.push_str(&format!("&commit_sha={sha}"));
         }
         if let Some(default_num_gpus) = args.default_num_gpus {
@@ -683,6 +996,10 @@ impl AxiomSdk {
 
         callback.on_header("Building Program");
 
+        if let (Some(rev), Some(sha)) = (&args.rev, &commit_sha) {
+            callback.on_field("Revision", &format!("{rev} ({sha})"));
+        }
+
         if let Some(id) = &config_id {
             callback.on_field("Config ID", id);
         } else if let Some(ConfigSource::ConfigPath(path)) = args.config_source.clone() {
@@ -695,6 +1012,8 @@ impl AxiomSdk {
             callback.on_field("Default Num GPUs", &default_num_gpus.to_string());
         }
 
+        callback.on_field("Source SHA-256", &tar_file.sha256);
+
         // Start progress tracking for upload
         callback.on_progress_start(
             "Uploading",
@@ -931,8 +1250,8 @@ impl AxiomSdk {
         callback.on_field("Config ID", config_id);
 
         // Build URL with query parameters. API 2.0 unified registration onto POST /programs
-        // (the /programs/upload-exe endpoint and the source-tarball build path were removed),
-        // and dropped the server-side bin_name param (the binary is chosen at build time).
+        // (the /programs/upload-exe endpoint was removed), and dropped the server-side
+        // bin_name param for pre-built uploads (the binary is chosen at build time).
         let mut url = format!("{}/programs?config_id={}", self.config.api_url, config_id);
 
         if let Some(project_id) = &args.project_id {
@@ -1008,7 +1327,7 @@ impl AxiomSdk {
     }
 }
 
-fn find_git_root(program_dir: impl AsRef<Path>) -> Result<std::path::PathBuf> {
+pub(crate) fn find_git_root(program_dir: impl AsRef<Path>) -> Result<std::path::PathBuf> {
     // Start from the current directory
     let mut current_dir = program_dir.as_ref().to_path_buf();
 
@@ -1027,6 +1346,46 @@ fn find_git_root(program_dir: impl AsRef<Path>) -> Result<std::path::PathBuf> {
     }
 }
 
+fn build_logs_path(program_id: &str) -> PathBuf {
+    PathBuf::from("axiom-artifacts")
+        .join(format!("program-{}", program_id))
+        .join("logs.txt")
+}
+
+/// Extract the last `max` compiler diagnostics from build logs. A diagnostic starts at
+/// an `error:` or `error[E...]:` line and runs until the next blank line; cargo's summary
+/// lines ("could not compile", "aborting due to") are skipped.
+fn last_compiler_errors(logs: &str, max: usize) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in logs.lines() {
+        let trimmed = line.trim_start();
+        let is_summary = trimmed.starts_with("error: could not compile")
+            || trimmed.starts_with("error: aborting due to");
+        let starts_error =
+            !is_summary && (trimmed.starts_with("error:") || trimmed.starts_with("error["));
+
+        if starts_error {
+            if let Some(lines) = current.take() {
+                errors.push(lines.join("\n"));
+            }
+            current = Some(vec![line]);
+        } else if trimmed.is_empty() || is_summary {
+            if let Some(lines) = current.take() {
+                errors.push(lines.join("\n"));
+            }
+        } else if let Some(lines) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    if let Some(lines) = current {
+        errors.push(lines.join("\n"));
+    }
+
+    let skip = errors.len().saturating_sub(max);
+    errors.split_off(skip)
+}
+
 fn find_cargo_workspace_root(program_dir: impl AsRef<Path>) -> Result<std::path::PathBuf> {
     // Start from the current directory
     let mut current_dir = program_dir.as_ref().to_path_buf();
@@ -1063,11 +1422,29 @@ fn find_cargo_workspace_root(program_dir: impl AsRef<Path>) -> Result<std::path:
     Err(eyre::eyre!("Not in a Cargo project"))
 }
 
-fn check_git_clean(git_root: impl AsRef<Path>) -> Result<bool> {
-    // Check if the git repository is clean (no uncommitted changes)
+fn git_tracked_files(git_root: impl AsRef<Path>) -> Result<HashSet<String>> {
+    let output = std::process::Command::new("git")
+        .current_dir(git_root.as_ref())
+        .args(["ls-files"])
+        .output()
+        .context("Failed to run 'git ls-files'")?;
+
+    if !output.status.success() {
+        eyre::bail!("Failed to get git tracked files");
+    }
+
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|s| s.to_string())
+        .collect())
+}
+
+/// Tracked files with uncommitted changes (staged or not), relative to the git root.
+/// Untracked files are ignored since they never end up in the archive.
+fn dirty_tracked_files(git_root: impl AsRef<Path>) -> Result<Vec<String>> {
     let output = std::process::Command::new("git")
         .current_dir(git_root.as_ref())
-        .args(["status", "--porcelain"])
+        .args(["status", "--porcelain", "-z"])
         .output()
         .context("Failed to run 'git status --porcelain'")?;
 
@@ -1075,11 +1452,90 @@ fn check_git_clean(git_root: impl AsRef<Path>) -> Result<bool> {
         eyre::bail!("Failed to check git status");
     }
 
-    // If output is empty, the repository is clean
-    Ok(output.stdout.is_empty())
+    Ok(parse_porcelain_tracked_changes(&String::from_utf8(
+        output.stdout,
+    )?))
+}
+
+/// Parse `git status --porcelain -z` output into the paths of changed tracked files.
+fn parse_porcelain_tracked_changes(output: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut entries = output.split('\0').filter(|e| !e.is_empty());
+    while let Some(entry) = entries.next() {
+        let Some((status, path)) = entry.split_at_checked(3) else {
+            continue;
+        };
+        if status.starts_with("??") {
+            continue;
+        }
+        // Renames and copies are followed by an extra entry holding the original path
+        if status.contains('R') || status.contains('C') {
+            entries.next();
+        }
+        paths.push(path.to_string());
+    }
+    paths
+}
+
+fn is_excluded(path: &str, exclude_patterns: &[String]) -> bool {
+    exclude_patterns.iter().any(|s| path.contains(s.as_str()))
 }
 
-fn get_git_commit_sha(git_root: impl AsRef<Path>) -> Result<String> {
+/// Resolve a commit, tag or branch to the full SHA of the commit it points to.
+fn resolve_git_rev(git_root: impl AsRef<Path>, rev: &str) -> Result<String> {
+    let output = std::process::Command::new("git")
+        .current_dir(git_root.as_ref())
+        .args(["rev-parse", "--verify", "--end-of-options"])
+        .arg(format!("{rev}^{{commit}}"))
+        .output()
+        .context("Failed to run 'git rev-parse'")?;
+
+    if !output.status.success() {
+        eyre::bail!("Unknown git revision: {rev}");
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// Export the tree of `sha` into `dest` exactly as `git archive` would.
+fn export_git_rev(git_root: impl AsRef<Path>, sha: &str, dest: &Path) -> Result<()> {
+    if dest.exists() {
+        std::fs::remove_dir_all(dest)?;
+    }
+    std::fs::create_dir_all(dest)?;
+
+    let mut child = std::process::Command::new("git")
+        .current_dir(git_root.as_ref())
+        .args(["archive", "--format=tar", sha])
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .context("Failed to run 'git archive'")?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_eyre("Failed to read 'git archive' output")?;
+    let unpacked = tar::Archive::new(stdout).unpack(dest);
+    let status = child.wait()?;
+    if !status.success() {
+        eyre::bail!("'git archive' failed for {sha}");
+    }
+    unpacked.with_context(|| format!("Failed to export {sha} to {}", dest.display()))
+}
+
+/// All files under `root`, relative to it.
+fn list_files(root: &Path) -> Result<HashSet<String>> {
+    let mut files = HashSet::new();
+    for entry in walkdir::WalkDir::new(root).min_depth(1) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            let relative = entry.path().strip_prefix(root)?;
+            files.insert(relative.to_string_lossy().to_string());
+        }
+    }
+    Ok(files)
+}
+
+pub(crate) fn get_git_commit_sha(git_root: impl AsRef<Path>) -> Result<String> {
     let git_dir = git_root.as_ref().join(".git");
 
     // Read .git/HEAD to get the current reference
@@ -1115,49 +1571,130 @@ fn get_git_commit_sha(git_root: impl AsRef<Path>) -> Result<String> {
     }
 }
 
-// The tarball contains everything in the git root of the guest program that's tracked by git.
-// Additionally, it does `cargo fetch` to pre-fetch dependencies so private dependencies are included.
+/// Collect the files of `source` that go into the archive, relative to its root: tracked
+/// files and those under `include_dirs`, minus anything matching `exclude_patterns`.
+fn collect_archive_files(
+    source: &ArchiveSource,
+    exclude_patterns: &[String],
+    include_dirs: &[String],
+) -> Result<Vec<PathBuf>> {
+    let tracked_files = &source.files;
+
+    let has_cargo_toml = tracked_files
+        .iter()
+        .any(|path| path.ends_with("Cargo.toml"));
+    let has_cargo_lock = tracked_files
+        .iter()
+        .any(|path| path.ends_with("Cargo.lock"));
+
+    if !has_cargo_toml || !has_cargo_lock {
+        eyre::bail!("Cargo.toml and Cargo.lock are required and should be tracked by git");
+    }
+
+    let walker = walkdir::WalkDir::new(&source.root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| {
+            let path = e.path();
+            let relative = path.strip_prefix(&source.root).unwrap_or(path);
+            let path_str = format!("./{}", relative.to_string_lossy());
+
+            // Exclude the tar file itself and any leftover prefetched dependencies
+            if path_str.ends_with("program.tar.gz") || path_str.contains(AXIOM_CARGO_HOME) {
+                return false;
+            }
+
+            // Check against user-provided exclusion patterns
+            let matches_exclusion = is_excluded(&path_str, exclude_patterns);
+            // Check if path is in user-provided include directories
+            let in_include_dir = include_dirs
+                .iter()
+                .any(|dir| path_str.starts_with(&format!("./{dir}")) || path_str.starts_with(dir));
+            // Check if file is tracked by git (directories are allowed to continue traversal)
+            let is_tracked = path.is_dir()
+                || tracked_files.contains(path_str.trim_start_matches("./"))
+                || in_include_dir;
+
+            is_tracked && !matches_exclusion
+        });
+
+    // TODO: print if verbose
+    Ok(walker
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            entry
+                .path()
+                .strip_prefix(&source.root)
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .collect())
+}
+
+// The tarball contains everything in the git root of the guest program that's tracked by git
+// (or, when building from a revision, everything in that revision's tree).
+// Additionally, it contains the pre-fetched dependencies in `cargo_home` so private dependencies
+// are included, placed at `axiom_cargo_home` in the cargo workspace root.
 fn create_tar_archive(
     program_dir: impl AsRef<Path>,
+    source: &ArchiveSource,
+    cargo_root_path: &str,
+    cargo_home: &Path,
+    files: &[PathBuf],
     keep_tarball: bool,
     exclude_patterns: &[String],
-    include_dirs: &[String],
-    openvm_rust_toolchain: Option<String>,
 ) -> Result<TarFile> {
     let tar_path = program_dir.as_ref().join("program.tar.gz");
     let tar_file = File::create(&tar_path)?;
-    let tar = TarFile {
+    let mut tar = TarFile {
         path: tar_path.to_string_lossy().to_string(),
         keep: keep_tarball,
+        sha256: String::new(),
     };
-    let enc = GzEncoder::new(tar_file, Compression::default());
-    let mut builder = Builder::new(enc);
-
-    // Find the git root directory
-    let git_root =
-        find_git_root(program_dir.as_ref()).context("Failed to find git root directory")?;
-    // Get the git root directory name
-    let dir_name = git_root
-        .file_name()
-        .ok_or_eyre("Failed to get git root directory name")?
-        .to_string_lossy()
-        .to_string();
 
-    let original_dir = std::env::current_dir()?;
+    let mut entries: Vec<(PathBuf, PathBuf)> = files
+        .iter()
+        .map(|path| (path.clone(), source.root.join(path)))
+        .collect();
 
-    // Pre-fetch dependencies to pull the private dependencies in the axiom_cargo_home (set it as CARGO_HOME) directory
-    let cargo_workspace_root = find_cargo_workspace_root(program_dir.as_ref())
-        .context("Failed to find cargo workspace root")?;
+    let cargo_home_in_archive = Path::new(cargo_root_path).join(AXIOM_CARGO_HOME);
+    for entry in walkdir::WalkDir::new(cargo_home)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+    {
+        let relative = entry.path().strip_prefix(cargo_home)?;
+        if relative == Path::new(FETCH_CACHE_COMPLETE_MARKER) {
+            continue;
+        }
+        let archive_path = cargo_home_in_archive.join(relative);
+        if is_excluded(&format!("./{}", archive_path.display()), exclude_patterns) {
+            continue;
+        }
+        entries.push((archive_path, entry.path().to_path_buf()));
+    }
 
-    std::env::set_current_dir(&cargo_workspace_root)?;
-    let axiom_cargo_home = cargo_workspace_root.join(AXIOM_CARGO_HOME);
-    std::fs::create_dir_all(&axiom_cargo_home)?;
+    write_deterministic_archive(tar_file, &source.prefix, entries)?;
 
-    // Clean up the axiom_cargo_home directory when the function exits
-    defer! {
-        std::fs::remove_dir_all(&axiom_cargo_home).ok();
-    }
+    tar.sha256 = sha256_file(&tar.path)?;
+    Ok(tar)
+}
 
+/// Pre-fetch the dependencies of the cargo workspace at `cargo_workspace_root` into a cargo
+/// home that is cached under `~/.axiom/cargo-cache`, keyed by the Cargo.lock and toolchains,
+/// and return its path. Fetching only happens on a cache miss or if `refresh` is set.
+///
+/// With `vendor_dir`, the workspace dependencies come from a `cargo vendor` directory (which
+/// `.cargo/config.toml` must replace crates-io with), so only the guest std dependencies
+/// are fetched.
+fn prefetch_dependencies(
+    cargo_workspace_root: &Path,
+    vendor_dir: Option<&str>,
+    refresh: bool,
+    openvm_rust_toolchain: Option<&str>,
+    callback: &dyn ProgressCallback,
+) -> Result<PathBuf> {
     // Get the required rust version from rust-toolchain.toml
     let toolchain_file_content = include_str!("../../../rust-toolchain.toml");
     let doc = toolchain_file_content
@@ -1166,122 +1703,327 @@ fn create_tar_archive(
     let required_version_str = doc["toolchain"]["channel"]
         .as_str()
         .ok_or_eyre("Could not find 'toolchain.channel' in rust-toolchain.toml")?;
+    let openvm_toolchain = openvm_rust_toolchain
+        .map(|tc| tc.to_string())
+        .unwrap_or_else(openvm_rust_toolchain_name);
+
+    if let Some(vendor_dir) = vendor_dir
+        && !cargo_workspace_root.join(vendor_dir).is_dir()
+    {
+        eyre::bail!(
+            "Vendor directory '{}' not found in {}",
+            vendor_dir,
+            cargo_workspace_root.display()
+        );
+    }
 
-    // Run cargo fetch with CARGO_HOME set to axiom_cargo_home
-    // Fetch 1: target = x86 linux which is the cloud machine
-    let status = std::process::Command::new("cargo")
-        .env("CARGO_HOME", &axiom_cargo_home)
-        .arg(format!("+{}", required_version_str))
-        .arg("fetch")
-        .arg("--target")
-        .arg("x86_64-unknown-linux-gnu")
-        .status()
-        .context("Failed to run 'cargo fetch'")?;
-    if !status.success() {
-        eyre::bail!("Failed to fetch cargo dependencies");
+    let key = fetch_cache_key(
+        cargo_workspace_root,
+        &[
+            required_version_str,
+            &openvm_toolchain,
+            vendor_dir.unwrap_or_default(),
+        ],
+    )?;
+    let cache_dir = get_axiom_dir()?.join(FETCH_CACHE_DIR).join(key);
+    if !refresh && cache_dir.join(FETCH_CACHE_COMPLETE_MARKER).exists() {
+        callback.on_info(&format!(
+            "Using cached dependencies from {}",
+            cache_dir.display()
+        ));
+        return Ok(cache_dir);
     }
 
-    // Fetch 2: Use local target as Cargo might have some dependencies for the local machine that's different from the cloud machine
-    // if local is not linux x86. And even though they are not needed in compilation, cargo tries to download them first.
-    let status = std::process::Command::new("cargo")
-        .env("CARGO_HOME", &axiom_cargo_home)
-        .arg(format!("+{}", required_version_str))
-        .arg("fetch")
-        .status()
-        .context("Failed to run 'cargo fetch'")?;
-    if !status.success() {
-        eyre::bail!("Failed to fetch cargo dependencies");
+    // Fetch into a staging directory and move it into place once complete, so that an
+    // interrupted fetch never leaves a partial cache behind
+    let staging_dir = cache_dir.with_extension(format!("tmp-{}", std::process::id()));
+    std::fs::remove_dir_all(&staging_dir).ok();
+    std::fs::create_dir_all(&staging_dir)?;
+    defer! {
+        std::fs::remove_dir_all(&staging_dir).ok();
+    }
+    // Use the user's cargo config and credentials for fetching (source replacement,
+    // private registries), but don't keep them in the cache
+    let copied_config = copy_user_cargo_config(&staging_dir)?;
+
+    callback.on_info("Fetching dependencies...");
+    if vendor_dir.is_some() {
+        // Check that the vendored sources cover the lockfile without touching the network
+        let status = std::process::Command::new("cargo")
+            .current_dir(cargo_workspace_root)
+            .env("CARGO_HOME", &staging_dir)
+            .arg(format!("+{}", required_version_str))
+            .args(["fetch", "--offline"])
+            .status()
+            .context("Failed to run 'cargo fetch'")?;
+        if !status.success() {
+            eyre::bail!(
+                "Vendored dependencies are incomplete. Make sure .cargo/config.toml replaces \
+                crates-io with the vendored sources (as printed by `cargo vendor`)."
+            );
+        }
+    } else {
+        // Run cargo fetch with CARGO_HOME set to the staging directory
+        // Fetch 1: target = x86 linux which is the cloud machine
+        let status = std::process::Command::new("cargo")
+            .current_dir(cargo_workspace_root)
+            .env("CARGO_HOME", &staging_dir)
+            .arg(format!("+{}", required_version_str))
+            .arg("fetch")
+            .arg("--target")
+            .arg("x86_64-unknown-linux-gnu")
+            .status()
+            .context("Failed to run 'cargo fetch'")?;
+        if !status.success() {
+            eyre::bail!("Failed to fetch cargo dependencies");
+        }
+
+        // Fetch 2: Use local target as Cargo might have some dependencies for the local machine that's different from the cloud machine
+        // if local is not linux x86. And even though they are not needed in compilation, cargo tries to download them first.
+        let status = std::process::Command::new("cargo")
+            .current_dir(cargo_workspace_root)
+            .env("CARGO_HOME", &staging_dir)
+            .arg(format!("+{}", required_version_str))
+            .arg("fetch")
+            .status()
+            .context("Failed to run 'cargo fetch'")?;
+        if !status.success() {
+            eyre::bail!("Failed to fetch cargo dependencies");
+        }
     }
 
     // Fetch 3: Run cargo fetch for some host dependencies (std stuffs)
     let mut cmd = openvm_guest_cargo_fetch_command();
-    if let Some(ref tc) = openvm_rust_toolchain {
+    if let Some(tc) = openvm_rust_toolchain {
         cmd.env("OPENVM_RUST_TOOLCHAIN", tc);
     }
     let status = cmd
-        .env("CARGO_HOME", &axiom_cargo_home)
+        .current_dir(cargo_workspace_root)
+        .env("CARGO_HOME", &staging_dir)
         .status()
         .context("Failed to run 'cargo fetch'")?;
     if !status.success() {
         eyre::bail!("Failed to fetch cargo dependencies");
     }
 
-    std::env::set_current_dir(&git_root)?;
-    // Get list of files tracked by git
-    let output = std::process::Command::new("git")
-        .args(["ls-files"])
-        .output()
-        .context("Failed to run 'git ls-files'")?;
+    for path in copied_config {
+        std::fs::remove_file(path)?;
+    }
+    File::create(staging_dir.join(FETCH_CACHE_COMPLETE_MARKER))?;
+    std::fs::remove_dir_all(&cache_dir).ok();
+    std::fs::rename(&staging_dir, &cache_dir)
+        .with_context(|| format!("Failed to populate {}", cache_dir.display()))?;
+    Ok(cache_dir)
+}
 
-    if !output.status.success() {
-        eyre::bail!("Failed to get git tracked files");
+/// Key of the fetch cache for a cargo workspace: a hash of its Cargo.lock, its
+/// `.cargo/config.toml` (which may replace sources) and `extra` inputs such as toolchains.
+fn fetch_cache_key(cargo_workspace_root: &Path, extra: &[&str]) -> Result<String> {
+    let lockfile = cargo_workspace_root.join("Cargo.lock");
+    let mut hasher = Sha256::new();
+    hasher.update(
+        std::fs::read(&lockfile)
+            .with_context(|| format!("Failed to read {}", lockfile.display()))?,
+    );
+    for config in [".cargo/config.toml", ".cargo/config"] {
+        if let Ok(content) = std::fs::read(cargo_workspace_root.join(config)) {
+            hasher.update(content);
+        }
+    }
+    for input in extra {
+        hasher.update([0]);
+        hasher.update(input.as_bytes());
     }
+    Ok(hex::encode(&hasher.finalize()[..16]))
+}
 
-    let tracked_files: std::collections::HashSet<String> = String::from_utf8(output.stdout)?
-        .lines()
-        .map(|s| s.to_string())
-        .collect();
+/// Copy the config and credentials files from the user's cargo home into `cargo_home`,
+/// returning the paths of the copies.
+fn copy_user_cargo_config(cargo_home: &Path) -> Result<Vec<PathBuf>> {
+    let user_cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+    let Some(user_cargo_home) = user_cargo_home else {
+        return Ok(Vec::new());
+    };
 
-    let has_cargo_toml = tracked_files
-        .iter()
-        .any(|path| path.ends_with("Cargo.toml"));
-    let has_cargo_lock = tracked_files
-        .iter()
-        .any(|path| path.ends_with("Cargo.lock"));
+    let mut copied = Vec::new();
+    for name in CARGO_HOME_CONFIG_FILES {
+        let source = user_cargo_home.join(name);
+        if source.is_file() {
+            let destination = cargo_home.join(name);
+            std::fs::copy(&source, &destination)
+                .with_context(|| format!("Failed to copy {}", source.display()))?;
+            copied.push(destination);
+        }
+    }
+    Ok(copied)
+}
 
-    if !has_cargo_toml || !has_cargo_lock {
-        eyre::bail!("Cargo.toml and Cargo.lock are required and should be tracked by git");
+/// Write `entries` (pairs of path in the archive and path on disk) into a gzipped tarball
+/// under `prefix/`, so that the same files always produce a byte-for-byte identical archive:
+/// entries are sorted, mtime/uid/gid/mode are normalized, and the gzip header has no
+/// timestamp or OS.
+fn write_deterministic_archive<W: Write>(
+    writer: W,
+    prefix: &str,
+    mut entries: Vec<(PathBuf, PathBuf)>,
+) -> Result<W> {
+    entries.sort();
+
+    let enc = GzBuilder::new()
+        .mtime(0)
+        .operating_system(255)
+        .write(writer, Compression::default());
+    let mut builder = Builder::new(enc);
+    builder.mode(HeaderMode::Deterministic);
+
+    for (relative_path, disk_path) in entries {
+        // Create path with the parent directory name
+        let archive_path = format!("{}/{}", prefix, relative_path.display());
+        let mut file = File::open(&disk_path)?;
+        builder.append_file(archive_path, &mut file)?;
     }
 
-    // Walk through the directory and add files to the archive
-    let walker = walkdir::WalkDir::new(".")
-        .min_depth(1)
-        .into_iter()
-        .filter_entry(|e| {
-            let path = e.path();
-            let path_str = path.to_string_lossy();
+    Ok(builder.into_inner()?.finish()?)
+}
 
-            // Exclude the tar file itself to avoid adding it to the tarball
-            if path_str.ends_with("program.tar.gz") {
-                return false;
-            }
+fn is_rust_project(dir: &Path) -> bool {
+    dir.join("Cargo.toml").exists()
+}
 
-            // Check against user-provided exclusion patterns
-            let matches_exclusion = exclude_patterns.iter().any(|s| path_str.contains(s));
-            // Check if path is in user-provided include directories
-            let in_include_dir = include_dirs
-                .iter()
-                .any(|dir| path_str.starts_with(&format!("./{dir}")) || path_str.starts_with(dir));
-            // Check if file is tracked by git (directories are allowed to continue traversal)
-            // Allow axiom_cargo_home directory even though it's not tracked by git
-            let is_tracked = path.is_dir()
-                || tracked_files.contains(path_str.trim_start_matches("./"))
-                || path_str.contains(AXIOM_CARGO_HOME)
-                || in_include_dir;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            is_tracked && !matches_exclusion
-        });
+    #[test]
+    fn test_parse_porcelain_tracked_changes() {
+        let output = " M src/main.rs\0?? notes.txt\0R  new.rs\0old.rs\0A  Cargo.lock\0 D gone.rs\0";
+        assert_eq!(
+            parse_porcelain_tracked_changes(output),
+            vec!["src/main.rs", "new.rs", "Cargo.lock", "gone.rs"]
+        );
+        assert!(parse_porcelain_tracked_changes("").is_empty());
+    }
 
-    for entry in walker.filter_map(Result::ok) {
-        let path = entry.path();
-        // TODO: print if verbose
-        if path.is_file() {
-            // Create path with the parent directory name
-            let relative_path = path.strip_prefix(".").unwrap();
-            let archive_path = format!("{}/{}", dir_name, relative_path.display());
+    #[test]
+    fn test_deterministic_archive() {
+        let root = std::env::temp_dir().join(format!("axiom-archive-test-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]\n").unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let entry = |path: &str| (PathBuf::from(path), root.join(path));
+
+        let first = write_deterministic_archive(
+            Vec::new(),
+            "guest",
+            vec![entry("src/main.rs"), entry("Cargo.toml")],
+        )
+        .unwrap();
+
+        // Touch a file: a new mtime and a different input order must not change the bytes
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        let second = write_deterministic_archive(
+            Vec::new(),
+            "guest",
+            vec![entry("Cargo.toml"), entry("src/main.rs")],
+        )
+        .unwrap();
+        std::fs::remove_dir_all(&root).ok();
+
+        assert_eq!(first, second);
+
+        let decoder = flate2::read::GzDecoder::new(first.as_slice());
+        let mut archive = tar::Archive::new(decoder);
+        let entries: Vec<_> = archive
+            .entries()
+            .unwrap()
+            .map(|e| {
+                let e = e.unwrap();
+                (
+                    e.path().unwrap().display().to_string(),
+                    e.header().uid().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("guest/Cargo.toml".to_string(), 0),
+                ("guest/src/main.rs".to_string(), 0)
+            ]
+        );
+    }
 
-            let mut file = File::open(path)?;
-            builder.append_file(archive_path, &mut file)?;
-        }
+    #[test]
+    fn test_is_excluded() {
+        let patterns = vec!["target/".to_string(), ".log".to_string()];
+        assert!(is_excluded("./target/debug/app", &patterns));
+        assert!(is_excluded("logs/run.log", &patterns));
+        assert!(!is_excluded("src/main.rs", &patterns));
     }
 
-    builder.finish()?;
-    // Change back to the original directory
-    std::env::set_current_dir(original_dir)?;
+    #[test]
+    fn test_program_update_serialization() {
+        let update = ProgramUpdate {
+            name: Some("fibonacci".to_string()),
+            labels: Some(vec!["release".to_string()]),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&update).unwrap();
+        assert_eq!(json, r#"{"name":"fibonacci","labels":["release"]}"#);
+    }
 
-    Ok(tar)
-}
+    #[test]
+    fn test_last_compiler_errors() {
+        let logs = "\
+   Compiling guest v0.1.0 (/app/guest)
+error[E0425]: cannot find value `x` in this scope
+ --> src/main.rs:3:5
+  |
+3 |     x
+  |     ^ not found in this scope
+
+warning: unused import
+error: expected `;`, found `}`
+ --> src/lib.rs:1:10
+error: aborting due to 2 previous errors
+error: could not compile `guest` (bin \"guest\") due to 2 previous errors
+";
+        let errors = last_compiler_errors(logs, 3);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("error[E0425]"));
+        assert!(errors[0].ends_with("not found in this scope"));
+        assert_eq!(
+            errors[1],
+            "error: expected `;`, found `}`\n --> src/lib.rs:1:10"
+        );
 
-fn is_rust_project(dir: &Path) -> bool {
-    dir.join("Cargo.toml").exists()
+        assert_eq!(last_compiler_errors(logs, 1), vec![errors[1].clone()]);
+        assert!(last_compiler_errors("Finished release", 3).is_empty());
+    }
+
+    #[test]
+    fn test_fetch_cache_key() {
+        let root =
+            std::env::temp_dir().join(format!("axiom-fetch-key-test-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("Cargo.lock"), "version = 4\n").unwrap();
+
+        let key = fetch_cache_key(&root, &["1.93.0", "nightly-2025-08-02"]).unwrap();
+        assert_eq!(
+            key,
+            fetch_cache_key(&root, &["1.93.0", "nightly-2025-08-02"]).unwrap()
+        );
+        assert_ne!(
+            key,
+            fetch_cache_key(&root, &["1.93.0", "nightly-2025-02-14"]).unwrap()
+        );
+
+        std::fs::write(root.join("Cargo.lock"), "version = 3\n").unwrap();
+        let changed = fetch_cache_key(&root, &["1.93.0", "nightly-2025-08-02"]).unwrap();
+        std::fs::remove_dir_all(&root).ok();
+        assert_ne!(key, changed);
+    }
 }
@@ -0,0 +1,190 @@
+//! ABI-encoding EVM proofs as calls to the OpenVM Halo2 verifier contract.
+
+use std::path::Path;
+
+use eyre::{Context, OptionExt, Result};
+use serde_json::{Value, json};
+use sha3::{Digest, Keccak256};
+
+use crate::public_values::{parse_public_values, to_hex};
+
+/// Signature of the verifier contract's `verify` function
+pub const VERIFY_SIGNATURE: &str = "verify(bytes,bytes,bytes32,bytes32)";
+
+/// The fields of an EVM proof file downloaded with `prove download --type evm`
+#[derive(Debug, Clone)]
+pub struct EvmProof {
+    pub version: String,
+    pub user_public_values: Vec<u8>,
+    pub app_exe_commit: [u8; 32],
+    pub app_vm_commit: [u8; 32],
+    pub accumulator: Vec<u8>,
+    pub proof: Vec<u8>,
+}
+
+impl EvmProof {
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read proof file {}", path.display()))?;
+        let json: Value = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid JSON in proof file {}", path.display()))?;
+        Self::from_json(&json)
+    }
+
+    pub fn from_json(json: &Value) -> Result<Self> {
+        let field = |value: Option<&Value>, name: &str| -> Result<Vec<u8>> {
+            let value = value.ok_or_else(|| eyre::eyre!("Missing '{name}' field in proof"))?;
+            parse_public_values(value).with_context(|| format!("Invalid '{name}' in proof"))
+        };
+        let commit = |name: &str| -> Result<[u8; 32]> {
+            let bytes = field(json.get(name), name)?;
+            <[u8; 32]>::try_from(bytes.as_slice())
+                .map_err(|_| eyre::eyre!("'{name}' must be 32 bytes, got {}", bytes.len()))
+        };
+        let proof_data = json
+            .get("proof_data")
+            .ok_or_eyre("Missing 'proof_data' field in proof")?;
+        Ok(EvmProof {
+            version: json
+                .get("version")
+                .and_then(Value::as_str)
+                .ok_or_eyre("Missing 'version' field in proof")?
+                .to_string(),
+            user_public_values: field(json.get("user_public_values"), "user_public_values")?,
+            app_exe_commit: commit("app_exe_commit")?,
+            app_vm_commit: commit("app_vm_commit")?,
+            accumulator: field(proof_data.get("accumulator"), "proof_data.accumulator")?,
+            proof: field(proof_data.get("proof"), "proof_data.proof")?,
+        })
+    }
+
+    /// The `proofData` argument of `verify`: the KZG accumulator followed by the proof
+    pub fn proof_data(&self) -> Vec<u8> {
+        [self.accumulator.as_slice(), self.proof.as_slice()].concat()
+    }
+
+    /// ABI-encoded `verify(publicValues, proofData, appExeCommit, appVmCommit)` call
+    pub fn verifier_calldata(&self) -> Vec<u8> {
+        let public_values = encode_bytes(&self.user_public_values);
+        let proof_data = encode_bytes(&self.proof_data());
+
+        let mut calldata = verify_selector().to_vec();
+        // Head: offsets of the two dynamic arguments, then the two static ones
+        calldata.extend(encode_uint(4 * 32));
+        calldata.extend(encode_uint(4 * 32 + public_values.len()));
+        calldata.extend(self.app_exe_commit);
+        calldata.extend(self.app_vm_commit);
+        calldata.extend(public_values);
+        calldata.extend(proof_data);
+        calldata
+    }
+
+    /// `cast call` command line calling the verifier with this proof. The address and
+    /// RPC URL default to shell variables.
+    pub fn cast_call_command(&self, verifier: Option<&str>, rpc_url: Option<&str>) -> String {
+        format!(
+            "cast call {} \"{VERIFY_SIGNATURE}\" {} {} {} {} --rpc-url {}",
+            verifier.map_or_else(|| "\"$VERIFIER_ADDRESS\"".to_string(), shell_quote),
+            to_hex(&self.user_public_values),
+            to_hex(&self.proof_data()),
+            to_hex(&self.app_exe_commit),
+            to_hex(&self.app_vm_commit),
+            rpc_url.map_or_else(|| "\"$RPC_URL\"".to_string(), shell_quote),
+        )
+    }
+
+    /// Test fixture with the arguments of `verify` and the complete calldata, readable
+    /// with Foundry's `vm.parseJson` or from Hardhat tests
+    pub fn fixture(&self) -> Value {
+        json!({
+            "version": self.version,
+            "signature": VERIFY_SIGNATURE,
+            "selector": to_hex(&verify_selector()),
+            "publicValues": to_hex(&self.user_public_values),
+            "proofData": to_hex(&self.proof_data()),
+            "appExeCommit": to_hex(&self.app_exe_commit),
+            "appVmCommit": to_hex(&self.app_vm_commit),
+            "calldata": to_hex(&self.verifier_calldata()),
+        })
+    }
+}
+
+fn verify_selector() -> [u8; 4] {
+    let hash = Keccak256::digest(VERIFY_SIGNATURE.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Quote a value as a single shell word, so the shell does not expand anything in it
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn encode_uint(value: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+/// Length-prefixed and zero-padded to a multiple of 32 bytes
+fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = encode_uint(bytes.len()).to_vec();
+    encoded.extend_from_slice(bytes);
+    encoded.resize(32 + bytes.len().div_ceil(32) * 32, 0);
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verifier_calldata() {
+        let proof = EvmProof::from_json(&json!({
+            "version": "v1.4",
+            "user_public_values": "0x01",
+            "app_exe_commit": format!("0x{}", "11".repeat(32)),
+            "app_vm_commit": format!("0x{}", "22".repeat(32)),
+            "proof_data": { "accumulator": "0xaa", "proof": "0xbbcc" },
+        }))
+        .unwrap();
+        let calldata = proof.verifier_calldata();
+        assert_eq!(hex::encode(&calldata[..4]), "24270d54");
+        let words: Vec<_> = calldata[4..].chunks(32).map(hex::encode).collect();
+        assert_eq!(words.len(), 8);
+        assert!(words[0].ends_with("80"));
+        assert!(words[1].ends_with("c0"));
+        assert_eq!(words[2], "11".repeat(32));
+        assert_eq!(words[3], "22".repeat(32));
+        assert!(words[4].ends_with("01"));
+        assert_eq!(words[5], format!("01{}", "00".repeat(31)));
+        assert!(words[6].ends_with("03"));
+        assert_eq!(words[7], format!("aabbcc{}", "00".repeat(29)));
+
+        let short_commit = json!({
+            "version": "v1.4",
+            "user_public_values": "0x",
+            "app_exe_commit": "0x11",
+            "app_vm_commit": "0x22",
+            "proof_data": { "accumulator": "0x", "proof": "0x" },
+        });
+        assert!(EvmProof::from_json(&short_commit).is_err());
+    }
+
+    #[test]
+    fn test_cast_call_command() {
+        let proof = EvmProof {
+            version: "v1.4".to_string(),
+            user_public_values: vec![1],
+            app_exe_commit: [0; 32],
+            app_vm_commit: [0; 32],
+            accumulator: Vec::new(),
+            proof: Vec::new(),
+        };
+        let command = proof.cast_call_command(None, None);
+        assert!(command.starts_with("cast call \"$VERIFIER_ADDRESS\" "));
+        assert!(command.ends_with(" --rpc-url \"$RPC_URL\""));
+        let command = proof.cast_call_command(Some("0xabc"), Some("https://rpc/?key=a'b;$(x)"));
+        assert!(command.starts_with("cast call '0xabc' "));
+        assert!(command.ends_with(" --rpc-url 'https://rpc/?key=a'\\''b;$(x)'"));
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fs::File, io::copy, path::PathBuf};
+use std::path::PathBuf;
 
 use bytes::Bytes;
 use eyre::{Context, OptionExt, Result};
@@ -42,48 +42,14 @@ impl PkDownloader {
         output_path: &str,
         callback: &dyn crate::ProgressCallback,
     ) -> Result<()> {
-        let path = std::path::Path::new(output_path);
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        let client = Client::new();
-
-        let mut response = client
-            .get(&self.download_url)
-            .send()
-            .context("Failed to download proving keys")?;
-
-        if response.status().is_success() {
-            let content_length = response.content_length();
-
-            callback.on_progress_start(
-                "Downloading proving key",
-                content_length,
-                crate::TransferDirection::Download,
-            );
-
-            let mut file = File::create(output_path)?;
-            crate::stream_response_to_file(
-                &mut response,
-                &mut file,
-                callback,
-                content_length.is_some(),
-            )?;
-            callback.on_progress_finish("✓ Key downloaded successfully");
-            Ok(())
-        } else if response.status().is_client_error() {
-            callback.on_progress_finish("");
-            let status = response.status();
-            let error_text = response.text()?;
-            Err(eyre::eyre!("Client error ({}): {}", status, error_text))
-        } else {
-            callback.on_progress_finish("");
-            Err(eyre::eyre!(
-                "Config status request failed with status: {}",
-                response.status()
-            ))
-        }
+        crate::download_resumable(
+            Client::new().get(&self.download_url),
+            std::path::Path::new(output_path),
+            Some("Downloading proving key"),
+            callback,
+            "Failed to download proving keys",
+        )?;
+        Ok(())
     }
 }
 
@@ -224,31 +190,9 @@ fn download_artifact(
     let client = Client::new();
     let api_key = config.api_key.as_ref().ok_or_eyre("API key not set")?;
 
-    let response = add_cli_version_header(client.get(&url).header(API_KEY_HEADER, api_key))
-        .send()
-        .context("Failed to send download request")?;
-
-    // Check if the request was successful
-    if response.status().is_success() {
-        let bytes = response.bytes()?;
-
-        if let Some(output_path) = output {
-            // Ensure parent directory exists
-            if let Some(parent) = output_path.parent() {
-                std::fs::create_dir_all(parent)
-                    .context(format!("Failed to create directory: {}", parent.display()))?;
-            }
-            let mut file = File::create(&output_path)
-                .context(format!("Failed to create output file: {output_path:?}"))?;
-            copy(&mut bytes.as_ref(), &mut file).context("Failed to write response to file")?;
-        }
-
-        Ok(bytes)
-    } else if response.status().is_client_error() {
-        let status = response.status();
-        let error_text = response.text()?;
-        eyre::bail!("Client error ({}): {}", status, error_text)
-    } else {
-        eyre::bail!("Download request failed with status: {}", response.status())
-    }
+    crate::download_file(
+        add_cli_version_header(client.get(&url).header(API_KEY_HEADER, api_key)),
+        output,
+        "Failed to send download request",
+    )
 }
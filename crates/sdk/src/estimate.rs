@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use eyre::Result;
+
+use crate::{
+    AxiomSdk, ProgressCallback, ProofType,
+    build::BuildSdk,
+    input::Input,
+    poll_sleep,
+    prove::{ProofListQuery, ProofStatus, ProveSdk},
+    run::{ExecutionStatus, RunArgs, RunSdk},
+    start_wait,
+    timeline::Timeline,
+};
+
+const ESTIMATE_POLLING_INTERVAL_SECS: u64 = 5;
+/// Number of recent proofs of the program to compare against
+const PAST_PROOFS_PAGE_SIZE: u32 = 20;
+
+pub trait EstimateSdk {
+    /// Estimate what proving a program would take by executing it in `meter` and
+    /// `segment` mode, without generating a proof.
+    fn estimate_proof(&self, args: EstimateArgs) -> Result<ProofEstimate>;
+}
+
+#[derive(Debug)]
+pub struct EstimateArgs {
+    pub program_id: String,
+    pub input: Option<Input>,
+    pub proof_type: ProofType,
+    /// Defaults to the program's `default_num_gpus`
+    pub num_gpus: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct ProofEstimate {
+    pub program_id: String,
+    pub proof_type: ProofType,
+    pub num_gpus: usize,
+    pub total_cycles: Option<u64>,
+    pub num_segments: Option<usize>,
+    pub cost: Option<u64>,
+    /// Most recent successful proofs of the same type, newest first
+    pub past_proofs: Vec<ProofStatus>,
+    /// Proving time extrapolated from past proofs with the same number of GPUs
+    pub estimated_duration: Option<Duration>,
+}
+
+impl EstimateSdk for AxiomSdk {
+    fn estimate_proof(&self, args: EstimateArgs) -> Result<ProofEstimate> {
+        self.estimate_proof_base(args, &*self.callback)
+    }
+}
+
+impl AxiomSdk {
+    pub fn estimate_proof_base(
+        &self,
+        args: EstimateArgs,
+        callback: &dyn ProgressCallback,
+    ) -> Result<ProofEstimate> {
+        let num_gpus = match args.num_gpus {
+            Some(num_gpus) => num_gpus,
+            None => self.get_build_status(&args.program_id)?.default_num_gpus,
+        };
+
+        // Both executions run at the same time on the server
+        let meter_id = self.execute_program_base(
+            RunArgs {
+                program_id: Some(args.program_id.clone()),
+                input: args.input.clone(),
+                mode: "meter".to_string(),
+            },
+            callback,
+        )?;
+        let segment_id = self.execute_program_base(
+            RunArgs {
+                program_id: Some(args.program_id.clone()),
+                input: args.input,
+                mode: "segment".to_string(),
+            },
+            callback,
+        )?;
+        let meter = self.wait_for_execution_status(&meter_id, callback)?;
+        let segment = self.wait_for_execution_status(&segment_id, callback)?;
+
+        let past_proofs = self
+            .list_proofs_with_query(
+                &args.program_id,
+                Some(1),
+                Some(PAST_PROOFS_PAGE_SIZE),
+                &ProofListQuery {
+                    state: Some("Succeeded".to_string()),
+                    proof_type: Some(args.proof_type.to_string()),
+                    sort: Some("-created_at".to_string()),
+                    ..Default::default()
+                },
+            )?
+            .items;
+
+        let total_cycles = meter.total_cycle.or(segment.total_cycle);
+        let estimated_duration =
+            total_cycles.and_then(|cycles| estimate_duration(&past_proofs, num_gpus, cycles));
+
+        Ok(ProofEstimate {
+            program_id: args.program_id,
+            proof_type: args.proof_type,
+            num_gpus,
+            total_cycles,
+            num_segments: segment.num_segments,
+            cost: meter.cost,
+            past_proofs,
+            estimated_duration,
+        })
+    }
+
+    /// Wait for an execution to finish without printing its results
+    fn wait_for_execution_status(
+        &self,
+        execution_id: &str,
+        callback: &dyn ProgressCallback,
+    ) -> Result<ExecutionStatus> {
+        let _wait = start_wait();
+        callback.on_spinner_start(&format!("Waiting for execution {execution_id}"));
+        loop {
+            let status = self.get_execution_status(execution_id)?;
+            match status.status.as_str() {
+                "Succeeded" => {
+                    callback.on_progress_finish(&format!(
+                        "✓ {} execution {} completed",
+                        status.mode, execution_id
+                    ));
+                    return Ok(status);
+                }
+                "Failed" => {
+                    callback.on_progress_finish("");
+                    eyre::bail!(
+                        "{} execution {} failed: {}",
+                        status.mode,
+                        execution_id,
+                        status.error_message.as_deref().unwrap_or("Unknown error")
+                    );
+                }
+                _ => poll_sleep(
+                    Duration::from_secs(ESTIMATE_POLLING_INTERVAL_SECS),
+                    callback,
+                )?,
+            }
+        }
+    }
+}
+
+/// Wall-clock proving time of a finished proof
+pub fn proof_duration(proof: &ProofStatus) -> Option<Duration> {
+    Timeline::from(proof).finished_run_time()
+}
+
+/// Scale the proving time per cycle of past proofs run with `num_gpus` GPUs to
+/// `total_cycles`. Returns `None` if there are no comparable proofs.
+fn estimate_duration(
+    past_proofs: &[ProofStatus],
+    num_gpus: usize,
+    total_cycles: u64,
+) -> Option<Duration> {
+    let (secs, cycles) = past_proofs
+        .iter()
+        .filter(|proof| proof.num_gpus == num_gpus)
+        .filter_map(|proof| Some((proof_duration(proof)?, proof.num_instructions?)))
+        .filter(|(_, cycles)| *cycles > 0)
+        .fold((0.0, 0u64), |(secs, cycles), (duration, n)| {
+            (secs + duration.as_secs_f64(), cycles + n)
+        });
+    (cycles > 0).then(|| Duration::from_secs_f64(secs / cycles as f64 * total_cycles as f64))
+}
+
+fn average_cells_used(past_proofs: &[ProofStatus]) -> Option<u64> {
+    let count = past_proofs.len() as u64;
+    (count > 0).then(|| {
+        past_proofs
+            .iter()
+            .map(|proof| proof.cells_used)
+            .sum::<u64>()
+            / count
+    })
+}
+
+impl ProofEstimate {
+    /// Average `cells_used` of the past proofs
+    pub fn average_cells_used(&self) -> Option<u64> {
+        average_cells_used(&self.past_proofs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn past_proof(num_gpus: usize, num_instructions: u64, secs: u64) -> ProofStatus {
+        ProofStatus {
+            id: "prf".to_string(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            state: "Succeeded".to_string(),
+            proof_type: "stark".to_string(),
+            program_uuid: "prg".to_string(),
+            error_message: None,
+            launched_at: Some("2025-01-01T00:00:00Z".to_string()),
+            terminated_at: Some(format!("2025-01-01T00:{:02}:{:02}Z", secs / 60, secs % 60)),
+            created_by: "test@example.com".to_string(),
+            cells_used: 1000,
+            num_instructions: Some(num_instructions),
+            num_gpus,
+            priority: 5,
+        }
+    }
+
+    #[test]
+    fn test_estimate_duration() {
+        let past = vec![
+            past_proof(1, 1_000, 100),
+            past_proof(1, 3_000, 300),
+            past_proof(4, 1_000, 10),
+        ];
+        // 400s for 4000 cycles on 1 GPU
+        assert_eq!(
+            estimate_duration(&past, 1, 2_000),
+            Some(Duration::from_secs(200))
+        );
+        assert_eq!(
+            estimate_duration(&past, 4, 5_000),
+            Some(Duration::from_secs(50))
+        );
+        assert_eq!(estimate_duration(&past, 2, 5_000), None);
+        assert_eq!(average_cells_used(&past), Some(1000));
+        assert_eq!(average_cells_used(&[]), None);
+    }
+}
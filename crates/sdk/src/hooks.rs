@@ -0,0 +1,131 @@
+//! Commands and webhooks run when a job that is being waited on finishes.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use eyre::{Context, Result};
+use reqwest::blocking::Client;
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::{ProgressCallback, add_cli_version_header};
+
+#[derive(Debug, Clone, Default)]
+pub struct CompletionHooks {
+    /// Shell command to run. It gets the status JSON on stdin and in `AXIOM_JOB_STATUS`,
+    /// and the job's kind, ID and final state in `AXIOM_JOB_KIND`, `AXIOM_JOB_ID` and
+    /// `AXIOM_JOB_STATE`.
+    pub on_complete: Option<String>,
+    /// URL the status JSON is POSTed to
+    pub notify_url: Option<String>,
+}
+
+impl CompletionHooks {
+    pub fn is_empty(&self) -> bool {
+        self.on_complete.is_none() && self.notify_url.is_none()
+    }
+
+    /// Run the hooks for a finished job. Hook failures are reported as warnings and
+    /// never fail the job.
+    pub(crate) fn run(
+        &self,
+        kind: &str,
+        id: &str,
+        state: &str,
+        status: &impl Serialize,
+        callback: &dyn ProgressCallback,
+    ) {
+        if self.is_empty() {
+            return;
+        }
+        let payload = json!({
+            "kind": kind,
+            "id": id,
+            "state": state,
+            "status": serde_json::to_value(status).unwrap_or(Value::Null),
+        });
+
+        if let Some(command) = &self.on_complete
+            && let Err(e) = run_command(command, kind, id, state, &payload)
+        {
+            callback.on_warning(&format!("--on-complete command failed: {e}"));
+        }
+        if let Some(url) = &self.notify_url {
+            match notify(url, &payload) {
+                Ok(()) => callback.on_info(&format!("Notified {url}")),
+                Err(e) => callback.on_warning(&format!("Failed to notify {url}: {e}")),
+            }
+        }
+    }
+}
+
+fn run_command(command: &str, kind: &str, id: &str, state: &str, payload: &Value) -> Result<()> {
+    let payload = payload.to_string();
+    let mut child = shell_command(command)
+        .env("AXIOM_JOB_KIND", kind)
+        .env("AXIOM_JOB_ID", id)
+        .env("AXIOM_JOB_STATE", state)
+        .env("AXIOM_JOB_STATUS", &payload)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run '{command}'"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The command may not read its stdin, so a broken pipe is not an error
+        let _ = stdin.write_all(payload.as_bytes());
+    }
+    let exit_status = child.wait()?;
+    if !exit_status.success() {
+        eyre::bail!("'{command}' exited with {exit_status}");
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+/// POST the payload to a third-party URL, so the API key is deliberately not sent
+fn notify(url: &str, payload: &Value) -> Result<()> {
+    let response = add_cli_version_header(Client::new().post(url).json(payload))
+        .send()
+        .context("Failed to send notification")?;
+    if !response.status().is_success() {
+        eyre::bail!("server responded with {}", response.status());
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_command_gets_status() {
+        let out = std::env::temp_dir().join(format!("axiom-hook-test-{}", std::process::id()));
+        let payload = json!({ "kind": "proof", "id": "prf_01", "state": "Succeeded" });
+        let command = format!(
+            "printf '%s %s ' \"$AXIOM_JOB_ID\" \"$AXIOM_JOB_STATE\" > {0} && cat >> {0}",
+            out.display()
+        );
+        run_command(&command, "proof", "prf_01", "Succeeded", &payload).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            format!("prf_01 Succeeded {payload}")
+        );
+        std::fs::remove_file(&out).unwrap();
+
+        assert!(run_command("exit 3", "proof", "prf_01", "Failed", &payload).is_err());
+    }
+}
@@ -1,25 +1,40 @@
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         Arc, OnceLock,
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
     },
+    time::{Duration, Instant},
 };
 
+use base64::Engine;
 use bytes::Bytes;
 use dirs::home_dir;
 use eyre::{Context, OptionExt, Result};
 use reqwest::blocking::{Client, RequestBuilder, Response};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
 
-use crate::input::decode_hex_string;
+use crate::{hooks::CompletionHooks, input::decode_hex_string};
 
+pub mod analyze;
+pub mod batch;
 pub mod build;
+pub mod bundle;
+pub mod calldata;
 pub mod config;
+pub mod estimate;
+pub mod hooks;
 pub mod input;
+pub mod lock;
+pub mod logs;
+pub mod pipeline;
 pub mod projects;
 pub mod prove;
+pub mod public_values;
 pub mod run;
+pub mod secrets;
+pub mod timeline;
 pub mod verify;
 
 pub const API_KEY_HEADER: &str = "Axiom-API-Key";
@@ -36,6 +51,77 @@ pub enum TransferDirection {
 }
 static CLI_VERSION: OnceLock<String> = OnceLock::new();
 
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static ACTIVE_WAITS: AtomicUsize = AtomicUsize::new(0);
+
+/// Error returned by the wait methods when [`interrupt`] is called while they are polling.
+/// The job itself keeps running on the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interrupted;
+
+impl std::fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Interrupted while waiting")
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// Make running wait methods stop polling and return [`Interrupted`]. Safe to call from
+/// a signal handler.
+pub fn interrupt() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Forget an earlier [`interrupt`] so that later waits poll normally again
+pub fn clear_interrupt() {
+    INTERRUPTED.store(false, Ordering::SeqCst);
+}
+
+/// Whether a wait method is currently polling, i.e. whether [`interrupt`] would stop it
+pub fn is_waiting() -> bool {
+    ACTIVE_WAITS.load(Ordering::SeqCst) > 0
+}
+
+/// Marks a wait as running until dropped
+pub struct WaitGuard;
+
+/// Start a wait that polls with [`poll_sleep`]. An interrupt left over from an earlier
+/// wait is cleared when no other wait is running.
+pub fn start_wait() -> WaitGuard {
+    if ACTIVE_WAITS.fetch_add(1, Ordering::SeqCst) == 0 {
+        clear_interrupt();
+    }
+    WaitGuard
+}
+
+impl Drop for WaitGuard {
+    fn drop(&mut self) {
+        ACTIVE_WAITS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Sleep between status checks, returning [`Interrupted`] as soon as [`interrupt`] is
+/// called. Any progress display is finished first so the terminal is left clean.
+pub fn poll_sleep(duration: Duration, callback: &dyn ProgressCallback) -> Result<()> {
+    let deadline = Instant::now() + duration;
+    loop {
+        if is_interrupted() {
+            callback.on_progress_finish("");
+            return Err(Interrupted.into());
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(());
+        }
+        std::thread::sleep((deadline - now).min(Duration::from_millis(100)));
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ProofType {
@@ -191,6 +277,7 @@ impl ProgressCallback for NoopCallback {
 pub struct AxiomSdk {
     pub config: AxiomConfig,
     callback: Box<dyn ProgressCallback>,
+    hooks: CompletionHooks,
 }
 
 impl AxiomSdk {
@@ -198,6 +285,7 @@ impl AxiomSdk {
         Self {
             config,
             callback: Box::new(NoopCallback),
+            hooks: CompletionHooks::default(),
         }
     }
 
@@ -205,6 +293,12 @@ impl AxiomSdk {
         self.callback = Box::new(callback);
         self
     }
+
+    /// Run `hooks` whenever a wait for a proof, build, execution or verification ends
+    pub fn with_completion_hooks(mut self, hooks: CompletionHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
 }
 
 impl Default for AxiomSdk {
@@ -212,6 +306,7 @@ impl Default for AxiomSdk {
         Self {
             config: AxiomConfig::default(),
             callback: Box::new(NoopCallback),
+            hooks: CompletionHooks::default(),
         }
     }
 }
@@ -395,6 +490,13 @@ pub fn authenticated_put(config: &AxiomConfig, url: &str) -> Result<RequestBuild
     Ok(add_cli_version_header(client.put(url)).header(API_KEY_HEADER, api_key))
 }
 
+pub fn authenticated_delete(config: &AxiomConfig, url: &str) -> Result<RequestBuilder> {
+    let client = Client::new();
+    let api_key = config.api_key.as_ref().ok_or_eyre("API key not set")?;
+
+    Ok(add_cli_version_header(client.delete(url)).header(API_KEY_HEADER, api_key))
+}
+
 /// Calculate a human-readable duration between two RFC3339 timestamps.
 ///
 /// Returns a formatted string like "5s", "2m 30s", or "1h 15m 30s".
@@ -423,19 +525,22 @@ pub fn calculate_duration(start: &str, end: &str) -> Result<String, String> {
     let end_time = DateTime::parse_from_rfc3339(end).map_err(|_| "Invalid end timestamp")?;
 
     let duration = end_time.signed_duration_since(start_time);
-    let total_seconds = duration.num_seconds();
+    Ok(format_duration(duration.num_seconds()))
+}
 
+/// Format a number of seconds like "1h 2m 3s"
+pub fn format_duration(total_seconds: i64) -> String {
     if total_seconds < 60 {
-        Ok(format!("{}s", total_seconds))
+        format!("{}s", total_seconds)
     } else if total_seconds < 3600 {
         let minutes = total_seconds / 60;
         let seconds = total_seconds % 60;
-        Ok(format!("{}m {}s", minutes, seconds))
+        format!("{}m {}s", minutes, seconds)
     } else {
         let hours = total_seconds / 3600;
         let minutes = (total_seconds % 3600) / 60;
         let seconds = total_seconds % 60;
-        Ok(format!("{}h {}m {}s", hours, minutes, seconds))
+        format!("{}h {}m {}s", hours, minutes, seconds)
     }
 }
 
@@ -458,14 +563,42 @@ pub fn send_request(request_builder: RequestBuilder, error_context: &str) -> Res
     handle_response(response)
 }
 
+/// An API request rejected with a 4xx status
+#[derive(Debug)]
+pub struct ClientError {
+    pub status: reqwest::StatusCode,
+    pub body: String,
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Client error ({}): {}", self.status, self.body)
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl ClientError {
+    pub(crate) fn from_response(response: Response) -> Result<Self> {
+        let status = response.status();
+        let body = response.text()?;
+        Ok(ClientError { status, body })
+    }
+}
+
+/// Whether a request failed because the resource doesn't exist
+pub fn is_not_found(error: &eyre::Report) -> bool {
+    error
+        .downcast_ref::<ClientError>()
+        .is_some_and(|error| error.status == reqwest::StatusCode::NOT_FOUND)
+}
+
 fn handle_json_response<T: DeserializeOwned>(response: Response) -> Result<T> {
     if response.status().is_success() {
         let result: T = response.json()?;
         Ok(result)
     } else if response.status().is_client_error() {
-        let status = response.status();
-        let error_text = response.text()?;
-        Err(eyre::eyre!("Client error ({}): {}", status, error_text))
+        Err(ClientError::from_response(response)?.into())
     } else {
         Err(eyre::eyre!(
             "Request failed with status: {}",
@@ -478,9 +611,7 @@ fn handle_response(response: Response) -> Result<()> {
     if response.status().is_success() {
         Ok(())
     } else if response.status().is_client_error() {
-        let status = response.status();
-        let error_text = response.text()?;
-        Err(eyre::eyre!("Client error ({}): {}", status, error_text))
+        Err(ClientError::from_response(response)?.into())
     } else {
         Err(eyre::eyre!(
             "Request failed with status: {}",
@@ -489,6 +620,23 @@ fn handle_response(response: Response) -> Result<()> {
     }
 }
 
+/// Compute the hex-encoded SHA-256 digest of a file, streaming it in `CHUNK_SIZE` chunks.
+pub fn sha256_file(path: impl AsRef<std::path::Path>) -> Result<String> {
+    let path = path.as_ref();
+    let mut file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open {} for hashing", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    loop {
+        let bytes_read = std::io::Read::read(&mut file, &mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
 /// A reader wrapper that tracks total bytes read via an atomic counter.
 /// Useful for monitoring upload progress from a separate thread.
 pub struct CountingReader<R: std::io::Read> {
@@ -511,26 +659,51 @@ impl<R: std::io::Read> std::io::Read for CountingReader<R> {
 ///
 /// When `has_content_length` is true, reports per-chunk progress updates;
 /// otherwise streams without progress updates. Always uses `CHUNK_SIZE` buffer.
+#[deprecated(
+    note = "use `download_resumable`, which resumes interrupted downloads and checks them"
+)]
 pub fn stream_response_to_file(
     response: &mut reqwest::blocking::Response,
     file: &mut std::fs::File,
     callback: &dyn ProgressCallback,
     has_content_length: bool,
+) -> Result<u64> {
+    copy_response_to_file(
+        response,
+        file,
+        0,
+        &|size| {
+            if has_content_length {
+                callback.on_progress_update(size);
+            }
+        },
+        &|size| format!("Download interrupted after {size} bytes"),
+    )
+}
+
+/// Copy a response body to `file` in `CHUNK_SIZE` chunks, counting from the `offset` bytes
+/// already in it. Returns the size of the file.
+fn copy_response_to_file(
+    response: &mut Response,
+    file: &mut std::fs::File,
+    offset: u64,
+    on_progress: &dyn Fn(u64),
+    interrupted: &dyn Fn(u64) -> String,
 ) -> Result<u64> {
     let mut buffer = vec![0u8; CHUNK_SIZE];
-    let mut downloaded = 0u64;
+    let mut size = offset;
     loop {
-        let bytes_read = std::io::Read::read(response, &mut buffer)?;
+        let bytes_read =
+            std::io::Read::read(response, &mut buffer).with_context(|| interrupted(size))?;
         if bytes_read == 0 {
             break;
         }
-        std::io::Write::write_all(file, &buffer[..bytes_read])?;
-        downloaded += bytes_read as u64;
-        if has_content_length {
-            callback.on_progress_update(downloaded);
-        }
+        std::io::Write::write_all(file, &buffer[..bytes_read])
+            .context("Failed to write response to file")?;
+        size += bytes_read as u64;
+        on_progress(size);
     }
-    Ok(downloaded)
+    Ok(size)
 }
 
 /// Stream a download directly to a file without buffering in memory.
@@ -539,32 +712,14 @@ pub fn download_file_streaming(
     output_path: PathBuf,
     error_context: &str,
 ) -> Result<()> {
-    let mut response = request_builder
-        .send()
-        .with_context(|| error_context.to_string())?;
-
-    if response.status().is_success() {
-        if let Some(parent) = output_path.parent() {
-            std::fs::create_dir_all(parent)
-                .context(format!("Failed to create directory: {}", parent.display()))?;
-        }
-        let file = std::fs::File::create(&output_path).context(format!(
-            "Failed to create output file: {}",
-            output_path.display()
-        ))?;
-        let mut writer = std::io::BufWriter::with_capacity(CHUNK_SIZE, file);
-        std::io::copy(&mut response, &mut writer).context("Failed to write response to file")?;
-        Ok(())
-    } else if response.status().is_client_error() {
-        let status = response.status();
-        let error_text = response.text()?;
-        Err(eyre::eyre!("Client error ({}): {}", status, error_text))
-    } else {
-        Err(eyre::eyre!(
-            "Download request failed with status: {}",
-            response.status()
-        ))
-    }
+    download_resumable(
+        request_builder,
+        &output_path,
+        None,
+        &NoopCallback,
+        error_context,
+    )?;
+    Ok(())
 }
 
 pub fn download_file(
@@ -572,29 +727,236 @@ pub fn download_file(
     output: Option<PathBuf>,
     error_context: &str,
 ) -> Result<Bytes> {
+    if let Some(output_path) = output {
+        download_resumable(
+            request_builder,
+            &output_path,
+            None,
+            &NoopCallback,
+            error_context,
+        )?;
+        let content = std::fs::read(&output_path)
+            .with_context(|| format!("Failed to read {}", output_path.display()))?;
+        return Ok(Bytes::from(content));
+    }
+
     let response = request_builder
         .send()
         .with_context(|| error_context.to_string())?;
+    let response = check_download_response(response)?;
+    response.bytes().context("Failed to read response body")
+}
 
-    if response.status().is_success() {
-        let content = response.bytes().context("Failed to read response body")?;
+/// Header carrying the hex-encoded SHA-256 of the whole file
+const SHA256_HEADER: &str = "x-checksum-sha256";
+/// Header S3 uses for the base64-encoded SHA-256 of the whole object
+const S3_SHA256_HEADER: &str = "x-amz-checksum-sha256";
+
+/// Path of the partial download for `output_path`: `<output_path>.part`
+pub fn part_path(output_path: &Path) -> PathBuf {
+    let mut name = output_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    output_path.with_file_name(name)
+}
+
+/// Path of the file holding the `ETag` or `Last-Modified` of the object being downloaded
+/// to the partial download, so that it is only resumed if the object hasn't changed
+fn part_validator_path(output_path: &Path) -> PathBuf {
+    let mut name = part_path(output_path).into_os_string();
+    name.push(".validator");
+    PathBuf::from(name)
+}
+
+/// The value to send as `If-Range` to resume a download of this response: its strong
+/// `ETag`, or else its `Last-Modified`
+fn if_range_validator(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let etag = headers
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .filter(|etag| !etag.starts_with("W/"));
+    etag.or_else(|| {
+        headers
+            .get(reqwest::header::LAST_MODIFIED)
+            .and_then(|value| value.to_str().ok())
+    })
+    .map(str::to_string)
+}
 
-        if let Some(output_path) = output {
-            // Ensure parent directory exists
-            if let Some(parent) = output_path.parent() {
-                std::fs::create_dir_all(parent)
-                    .context(format!("Failed to create directory: {}", parent.display()))?;
+fn remove_partial_download(output_path: &Path) -> Result<()> {
+    for path in [part_path(output_path), part_validator_path(output_path)] {
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(e).context(format!("Failed to remove {}", path.display()));
             }
-            let mut file = std::fs::File::create(&output_path).context(format!(
-                "Failed to create output file: {}",
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Download to `output_path` so that an interrupted download never leaves a corrupt
+/// file behind and can be resumed.
+///
+/// The body is written to `<output_path>.part`. If that file already exists, only the
+/// rest is requested with a `Range` header, and an `If-Range` header with the `ETag` or
+/// `Last-Modified` saved when the download started, so that the server sends the whole
+/// object again if it changed. A partial file without a saved validator is discarded.
+/// Once complete, the file's size and SHA-256
+/// are checked against what the server reported, if anything, and it is renamed into
+/// place. If `progress_message` is set, progress is reported through `callback`.
+/// Returns the size of the file.
+pub fn download_resumable(
+    request_builder: RequestBuilder,
+    output_path: &Path,
+    progress_message: Option<&str>,
+    callback: &dyn ProgressCallback,
+    error_context: &str,
+) -> Result<u64> {
+    if let Some(parent) = output_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)
+            .context(format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let part_path = part_path(output_path);
+    let validator_path = part_validator_path(output_path);
+    let mut resume_from = std::fs::metadata(&part_path).map_or(0, |metadata| metadata.len());
+    let validator = std::fs::read_to_string(&validator_path).ok();
+    if resume_from > 0 && validator.is_none() {
+        // Without a validator there is no telling whether the bytes belong to this object
+        remove_partial_download(output_path)?;
+        resume_from = 0;
+    }
+    let retry_builder = request_builder.try_clone();
+
+    let request_builder = match &validator {
+        Some(validator) if resume_from > 0 => request_builder
+            .header(reqwest::header::RANGE, format!("bytes={resume_from}-"))
+            .header(reqwest::header::IF_RANGE, validator.trim()),
+        _ => request_builder,
+    };
+    let response = request_builder
+        .send()
+        .with_context(|| error_context.to_string())?;
+
+    // The partial file is already complete or stale: start over
+    if resume_from > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        remove_partial_download(output_path)?;
+        let Some(retry_builder) = retry_builder else {
+            eyre::bail!(
+                "Partial download of {} was invalid, retry the download",
                 output_path.display()
-            ))?;
+            );
+        };
+        return download_resumable(
+            retry_builder,
+            output_path,
+            progress_message,
+            callback,
+            error_context,
+        );
+    }
+    let mut response = check_download_response(response)?;
+
+    let headers = response.headers();
+    let resumed = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let (offset, total_size) = if resumed {
+        let content_range = headers
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_eyre("Partial response without a Content-Range header")?;
+        let (start, total) = parse_content_range(content_range)
+            .ok_or_else(|| eyre::eyre!("Invalid Content-Range header: {content_range}"))?;
+        if start != resume_from {
+            eyre::bail!("Server resumed the download at byte {start} instead of {resume_from}");
+        }
+        (start, total)
+    } else {
+        (0, response.content_length())
+    };
+    let expected_sha256 = expected_sha256(headers);
+    if !resumed {
+        // A full response starts the partial download over, for the object it came from
+        match if_range_validator(headers) {
+            Some(validator) => std::fs::write(&validator_path, validator)
+                .context(format!("Failed to write {}", validator_path.display()))?,
+            None => remove_partial_download(output_path)?,
+        }
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&part_path)
+        .context(format!(
+            "Failed to create output file: {}",
+            part_path.display()
+        ))?;
 
-            std::io::copy(&mut content.as_ref(), &mut file)
-                .context("Failed to write response to file")?;
+    if let Some(message) = progress_message {
+        callback.on_progress_start(message, total_size, TransferDirection::Download);
+        if offset > 0 {
+            callback.on_progress_update(offset);
+        }
+    }
+    let result = (|| {
+        let size = copy_response_to_file(
+            &mut response,
+            &mut file,
+            offset,
+            &|size| {
+                if progress_message.is_some() && total_size.is_some() {
+                    callback.on_progress_update(size);
+                }
+            },
+            &|size| {
+                format!("Download interrupted after {size} bytes, run the command again to resume")
+            },
+        )?;
+        file.sync_all()?;
+        drop(file);
+
+        if let Some(total_size) = total_size
+            && size != total_size
+        {
+            eyre::bail!(
+                "Download incomplete: received {size} of {total_size} bytes, run the command again to resume"
+            );
+        }
+        if let Some(expected) = &expected_sha256 {
+            let actual = sha256_file(&part_path)?;
+            if !actual.eq_ignore_ascii_case(expected) {
+                remove_partial_download(output_path)?;
+                eyre::bail!(
+                    "Checksum mismatch for {}: expected SHA-256 {expected}, got {actual}",
+                    output_path.display()
+                );
+            }
         }
+        std::fs::rename(&part_path, output_path).context(format!(
+            "Failed to move download to {}",
+            output_path.display()
+        ))?;
+        remove_partial_download(output_path)?;
+        Ok(size)
+    })();
+
+    if progress_message.is_some() {
+        callback.on_progress_finish(if result.is_ok() {
+            "✓ Download complete"
+        } else {
+            ""
+        });
+    }
+    result
+}
 
-        Ok(content)
+/// Turn error statuses into errors, including the body of client errors
+pub(crate) fn check_download_response(response: Response) -> Result<Response> {
+    if response.status().is_success() {
+        Ok(response)
     } else if response.status().is_client_error() {
         let status = response.status();
         let error_text = response.text()?;
@@ -607,10 +969,48 @@ pub fn download_file(
     }
 }
 
+/// Parse `bytes <start>-<end>/<total>` into the start offset and the total size, if known
+pub(crate) fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _end) = range.split_once('-')?;
+    let total = match total {
+        "*" => None,
+        total => Some(total.parse().ok()?),
+    };
+    Some((start.parse().ok()?, total))
+}
+
+/// Hex-encoded SHA-256 of the whole file, if the server sent one
+fn expected_sha256(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    if let Some(value) = headers.get(SHA256_HEADER) {
+        return value.to_str().ok().map(str::to_string);
+    }
+    let value = headers.get(S3_SHA256_HEADER)?.to_str().ok()?;
+    base64::engine::general_purpose::STANDARD
+        .decode(value)
+        .ok()
+        .map(hex::encode)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_interrupt_is_cleared_by_next_wait() {
+        interrupt();
+        {
+            let _wait = start_wait();
+            assert!(is_waiting());
+            assert!(!is_interrupted());
+            interrupt();
+            assert!(poll_sleep(Duration::from_secs(1), &NoopCallback).is_err());
+        }
+        assert!(!is_waiting());
+        let _wait = start_wait();
+        assert!(poll_sleep(Duration::ZERO, &NoopCallback).is_ok());
+    }
+
     #[test]
     fn test_config_default() {
         let config = AxiomConfig::default();
@@ -684,6 +1084,12 @@ mod tests {
         assert_eq!(result, "45s");
     }
 
+    #[test]
+    fn test_duration_calculation_reversed() {
+        let result = calculate_duration("2023-01-01T12:00:45Z", "2023-01-01T12:00:00Z").unwrap();
+        assert_eq!(result, "-45s");
+    }
+
     #[test]
     fn test_duration_calculation_hours() {
         let start = "2023-01-01T12:00:00Z";
@@ -692,4 +1098,41 @@ mod tests {
         let result = calculate_duration(start, end).unwrap();
         assert_eq!(result, "2h 15m 30s");
     }
+
+    #[test]
+    fn test_if_range_validator() {
+        use reqwest::header::{ETAG, HeaderMap, LAST_MODIFIED};
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(if_range_validator(&headers), None);
+        headers.insert(
+            LAST_MODIFIED,
+            "Wed, 21 Oct 2025 07:28:00 GMT".parse().unwrap(),
+        );
+        headers.insert(ETAG, "W/\"weak\"".parse().unwrap());
+        assert_eq!(
+            if_range_validator(&headers).as_deref(),
+            Some("Wed, 21 Oct 2025 07:28:00 GMT")
+        );
+        headers.insert(ETAG, "\"abc\"".parse().unwrap());
+        assert_eq!(if_range_validator(&headers).as_deref(), Some("\"abc\""));
+        assert_eq!(
+            part_validator_path(Path::new("keys/app.pk")),
+            PathBuf::from("keys/app.pk.part.validator")
+        );
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(
+            parse_content_range("bytes 100-199/200"),
+            Some((100, Some(200)))
+        );
+        assert_eq!(parse_content_range("bytes 100-199/*"), Some((100, None)));
+        assert_eq!(parse_content_range("bytes */200"), None);
+        assert_eq!(
+            part_path(Path::new("keys/app.pk")),
+            PathBuf::from("keys/app.pk.part")
+        );
+    }
 }
@@ -0,0 +1,270 @@
+use std::path::{Path, PathBuf};
+
+use eyre::{Context, OptionExt, Result};
+use toml_edit::{DocumentMut, value};
+
+use crate::{
+    AxiomSdk, CLI_VERSION,
+    build::{BuildSdk, BuildStatus, find_git_root, get_git_commit_sha},
+};
+
+pub const LOCK_FILE_NAME: &str = "axiom.lock";
+const LOCK_FILE_VERSION: i64 = 1;
+
+pub trait LockSdk {
+    /// Collect the identity of a finished build into an [`AxiomLock`]
+    fn create_lock(
+        &self,
+        program_dir: impl AsRef<Path>,
+        program_id: &str,
+        bin: Option<&str>,
+    ) -> Result<AxiomLock>;
+}
+
+/// Contents of `axiom.lock`, which pins an uploaded program to the sources and
+/// config it was built from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AxiomLock {
+    pub program_id: String,
+    pub program_hash: String,
+    pub config_uuid: String,
+    /// Hex-encoded app EXE commitment
+    pub app_exe_commit: String,
+    /// Git commit of the program directory at build time, if it is a git repository
+    pub git_commit: Option<String>,
+    pub bin: Option<String>,
+    pub cli_version: Option<String>,
+}
+
+impl AxiomLock {
+    pub fn path(program_dir: impl AsRef<Path>) -> PathBuf {
+        program_dir.as_ref().join(LOCK_FILE_NAME)
+    }
+
+    /// Read the lock file from `program_dir`, returning `None` if there isn't one.
+    pub fn read(program_dir: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = Self::path(program_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_toml_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+            .map(Some)
+    }
+
+    pub fn write(&self, program_dir: impl AsRef<Path>) -> Result<PathBuf> {
+        let path = Self::path(program_dir);
+        std::fs::write(&path, self.to_toml_string())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn to_toml_string(&self) -> String {
+        let mut doc = DocumentMut::new();
+        doc["version"] = value(LOCK_FILE_VERSION);
+        doc["program_id"] = value(&self.program_id);
+        doc["program_hash"] = value(&self.program_hash);
+        doc["config_uuid"] = value(&self.config_uuid);
+        doc["app_exe_commit"] = value(&self.app_exe_commit);
+        if let Some(git_commit) = &self.git_commit {
+            doc["git_commit"] = value(git_commit);
+        }
+        if let Some(bin) = &self.bin {
+            doc["bin"] = value(bin);
+        }
+        if let Some(cli_version) = &self.cli_version {
+            doc["cli_version"] = value(cli_version);
+        }
+        format!(
+            "# This file is automatically @generated by cargo-axiom.\n\
+             # It is not intended for manual editing.\n{doc}"
+        )
+    }
+
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        let doc = content.parse::<DocumentMut>()?;
+        let version = doc
+            .get("version")
+            .and_then(|v| v.as_integer())
+            .ok_or_eyre("Missing 'version' field")?;
+        if version != LOCK_FILE_VERSION {
+            eyre::bail!("Unsupported lock file version: {version}");
+        }
+        let required = |key: &str| -> Result<String> {
+            doc.get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .ok_or_else(|| eyre::eyre!("Missing '{key}' field"))
+        };
+        let optional = |key: &str| doc.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+        Ok(Self {
+            program_id: required("program_id")?,
+            program_hash: required("program_hash")?,
+            config_uuid: required("config_uuid")?,
+            app_exe_commit: required("app_exe_commit")?,
+            git_commit: optional("git_commit"),
+            bin: optional("bin"),
+            cli_version: optional("cli_version"),
+        })
+    }
+
+    /// Differences in the program, config and app commitment recorded by `other`,
+    /// one line per field. Program IDs and git commits change with every rebuild, so they
+    /// are not compared.
+    pub fn changes(&self, other: &AxiomLock) -> Vec<String> {
+        [
+            ("program_hash", &self.program_hash, &other.program_hash),
+            ("config_uuid", &self.config_uuid, &other.config_uuid),
+            (
+                "app_exe_commit",
+                &self.app_exe_commit,
+                &other.app_exe_commit,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, locked, built)| locked != built)
+        .map(|(field, locked, built)| format!("{field}: locked {locked}, built {built}"))
+        .collect()
+    }
+
+    /// Check that a build produced the same program and config as the one recorded
+    /// in the lock file.
+    pub fn verify_build(&self, build_status: &BuildStatus) -> Result<()> {
+        let mut mismatches = Vec::new();
+        if build_status.program_hash != self.program_hash {
+            mismatches.push(format!(
+                "program_hash: locked {}, built {}",
+                self.program_hash, build_status.program_hash
+            ));
+        }
+        if build_status.config_uuid != self.config_uuid {
+            mismatches.push(format!(
+                "config_uuid: locked {}, built {}",
+                self.config_uuid, build_status.config_uuid
+            ));
+        }
+        if !mismatches.is_empty() {
+            eyre::bail!(
+                "Build of program {} does not match {}:\n  {}",
+                build_status.id,
+                LOCK_FILE_NAME,
+                mismatches.join("\n  ")
+            );
+        }
+        Ok(())
+    }
+}
+
+impl LockSdk for AxiomSdk {
+    fn create_lock(
+        &self,
+        program_dir: impl AsRef<Path>,
+        program_id: &str,
+        bin: Option<&str>,
+    ) -> Result<AxiomLock> {
+        let build_status = self.get_build_status(program_id)?;
+        let app_exe_commit = self.get_app_exe_commit(program_id)?;
+        let git_commit = find_git_root(program_dir.as_ref())
+            .and_then(get_git_commit_sha)
+            .ok();
+
+        Ok(AxiomLock {
+            program_id: build_status.id,
+            program_hash: build_status.program_hash,
+            config_uuid: build_status.config_uuid,
+            app_exe_commit: hex::encode(app_exe_commit),
+            git_commit,
+            bin: bin.map(|b| b.to_string()),
+            cli_version: CLI_VERSION.get().cloned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_lock() -> AxiomLock {
+        AxiomLock {
+            program_id: "prg_01".to_string(),
+            program_hash: "abcd".to_string(),
+            config_uuid: "cfg_01".to_string(),
+            app_exe_commit: "00ff".to_string(),
+            git_commit: Some("0123456789abcdef0123456789abcdef01234567".to_string()),
+            bin: None,
+            cli_version: Some("2.0.0".to_string()),
+        }
+    }
+
+    fn sample_build_status(program_hash: &str, config_uuid: &str) -> BuildStatus {
+        BuildStatus {
+            id: "prg_02".to_string(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            status: "ready".to_string(),
+            config_uuid: config_uuid.to_string(),
+            error_message: None,
+            name: "test".to_string(),
+            created_by: "test@example.com".to_string(),
+            last_active_at: "2025-01-01T00:00:00Z".to_string(),
+            launched_at: None,
+            terminated_at: None,
+            program_hash: program_hash.to_string(),
+            openvm_config: String::new(),
+            cells_used: 0,
+            proofs_run: 0,
+            project_id: "prj_01".to_string(),
+            project_name: "Test Project".to_string(),
+            default_num_gpus: 1,
+            labels: Vec::new(),
+            archived: false,
+        }
+    }
+
+    #[test]
+    fn test_lock_roundtrip() {
+        let lock = sample_lock();
+        let parsed = AxiomLock::from_toml_str(&lock.to_toml_string()).unwrap();
+        assert_eq!(lock, parsed);
+    }
+
+    #[test]
+    fn test_lock_rejects_unknown_version() {
+        let content = sample_lock()
+            .to_toml_string()
+            .replace("version = 1", "version = 2");
+        assert!(AxiomLock::from_toml_str(&content).is_err());
+    }
+
+    #[test]
+    fn test_verify_build() {
+        let lock = sample_lock();
+        assert!(
+            lock.verify_build(&sample_build_status("abcd", "cfg_01"))
+                .is_ok()
+        );
+
+        let err = lock
+            .verify_build(&sample_build_status("beef", "cfg_01"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("program_hash"));
+        assert!(!err.contains("config_uuid"));
+    }
+
+    #[test]
+    fn test_lock_changes() {
+        let lock = sample_lock();
+        let mut rebuilt = sample_lock();
+        rebuilt.program_id = "prg_02".to_string();
+        rebuilt.git_commit = None;
+        assert!(lock.changes(&rebuilt).is_empty());
+
+        rebuilt.config_uuid = "cfg_02".to_string();
+        assert_eq!(
+            lock.changes(&rebuilt),
+            vec!["config_uuid: locked cfg_01, built cfg_02"]
+        );
+    }
+}
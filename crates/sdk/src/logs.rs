@@ -0,0 +1,253 @@
+//! Incremental log fetching for `logs --follow`.
+
+use std::{io::Write, path::Path, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use eyre::{Context, Result};
+use reqwest::{StatusCode, blocking::Response};
+
+use crate::{
+    AxiomConfig, AxiomSdk, ProgressCallback, authenticated_get, check_download_response,
+    parse_content_range, poll_sleep, start_wait,
+};
+
+const LOGS_POLLING_INTERVAL_SECS: u64 = 5;
+
+/// Which log lines `logs --follow` prints
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Only print lines logged at or after this time. Lines without a timestamp take
+    /// the timestamp of the line before them.
+    pub since: Option<DateTime<Utc>>,
+    /// Only print lines containing this text
+    pub grep: Option<String>,
+}
+
+impl LogFilter {
+    fn matches(&self, line: &str, timestamp: Option<DateTime<Utc>>) -> bool {
+        if let Some(since) = self.since
+            && timestamp.is_none_or(|timestamp| timestamp < since)
+        {
+            return false;
+        }
+        self.grep
+            .as_deref()
+            .is_none_or(|pattern| line.contains(pattern))
+    }
+}
+
+/// Reads a growing log file, fetching only the bytes appended since the last read with
+/// `Range` requests
+struct LogTail {
+    url: String,
+    filter: LogFilter,
+    /// Number of bytes of the log read so far
+    offset: usize,
+    /// Start of a line whose end has not been written yet
+    partial: Vec<u8>,
+    /// Timestamp of the last line that had one
+    last_timestamp: Option<DateTime<Utc>>,
+    /// The whole log read so far, if it is kept to be saved
+    log: Option<Vec<u8>>,
+}
+
+impl LogTail {
+    fn new(url: String, filter: LogFilter, keep_log: bool) -> Self {
+        Self {
+            url,
+            filter,
+            offset: 0,
+            partial: Vec::new(),
+            last_timestamp: None,
+            log: keep_log.then(Vec::new),
+        }
+    }
+
+    fn fetch(&self, config: &AxiomConfig, from: usize) -> Result<Response> {
+        let mut request = authenticated_get(config, &self.url)?;
+        if from > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={from}-"));
+        }
+        request.send().context("Failed to download logs")
+    }
+
+    /// Fetch what was appended to the log and write the complete lines that match the filter
+    fn poll(&mut self, config: &AxiomConfig, out: &mut impl Write) -> Result<()> {
+        let response = self.fetch(config, self.offset)?;
+        let new_bytes = match response.status() {
+            // Nothing was appended
+            StatusCode::RANGE_NOT_SATISFIABLE => return Ok(()),
+            StatusCode::PARTIAL_CONTENT if self.range_start(&response) == Some(self.offset) => {
+                response.bytes()?
+            }
+            // The server answered a different range, fetch the whole log instead
+            StatusCode::PARTIAL_CONTENT => {
+                let logs = check_download_response(self.fetch(config, 0)?)?.bytes()?;
+                self.skip_read(logs)
+            }
+            _ => {
+                let logs = check_download_response(response)?.bytes()?;
+                self.skip_read(logs)
+            }
+        };
+        self.offset += new_bytes.len();
+        if let Some(log) = &mut self.log {
+            log.extend_from_slice(&new_bytes);
+        }
+        self.partial.extend_from_slice(&new_bytes);
+
+        while let Some(end) = self.partial.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.partial.drain(..=end).collect();
+            self.write_line(&line, out)?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    fn range_start(&self, response: &Response) -> Option<usize> {
+        let content_range = response
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)?
+            .to_str()
+            .ok()?;
+        let (start, _) = parse_content_range(content_range)?;
+        usize::try_from(start).ok()
+    }
+
+    /// The part of the whole log that was not read yet
+    fn skip_read(&mut self, logs: Bytes) -> Bytes {
+        if logs.len() < self.offset {
+            // The logs start over if the job was restarted
+            self.offset = 0;
+            self.partial.clear();
+            if let Some(log) = &mut self.log {
+                log.clear();
+            }
+            logs
+        } else {
+            logs.slice(self.offset..)
+        }
+    }
+
+    /// Write the last line of a log that does not end with a newline
+    fn finish(&mut self, out: &mut impl Write) -> Result<()> {
+        if !self.partial.is_empty() {
+            let mut line = std::mem::take(&mut self.partial);
+            line.push(b'\n');
+            self.write_line(&line, out)?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    fn write_line(&mut self, line: &[u8], out: &mut impl Write) -> Result<()> {
+        let text = String::from_utf8_lossy(line);
+        if let Some(timestamp) = parse_log_timestamp(&text) {
+            self.last_timestamp = Some(timestamp);
+        }
+        if self.filter.matches(&text, self.last_timestamp) {
+            out.write_all(line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the timestamp a log line starts with, either RFC3339 or `YYYY-MM-DD HH:MM:SS`
+/// in UTC, optionally in square brackets
+pub(crate) fn parse_log_timestamp(line: &str) -> Option<DateTime<Utc>> {
+    let line = line.trim_start().trim_start_matches('[');
+    let mut words = line.split_whitespace();
+    let first = words.next()?.trim_end_matches(']');
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(first) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    let time = words.next()?.trim_end_matches(']');
+    NaiveDateTime::parse_from_str(&format!("{first} {time}"), "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|timestamp| timestamp.and_utc())
+}
+
+impl AxiomSdk {
+    /// Print the lines of the log at `url` matching `filter` as they are written, until
+    /// `finished` returns the final status of the job. The whole log is also returned if
+    /// `keep_log` is set.
+    pub(crate) fn follow_logs<T>(
+        &self,
+        url: String,
+        filter: &LogFilter,
+        keep_log: bool,
+        callback: &dyn ProgressCallback,
+        mut finished: impl FnMut() -> Result<Option<T>>,
+    ) -> Result<(T, Option<Vec<u8>>)> {
+        let _wait = start_wait();
+        let mut tail = LogTail::new(url, filter.clone(), keep_log);
+        let mut stdout = std::io::stdout();
+        loop {
+            // Check the status first so that the logs fetched after it are complete
+            let status = finished()?;
+            tail.poll(&self.config, &mut stdout)?;
+            if let Some(status) = status {
+                tail.finish(&mut stdout)?;
+                return Ok((status, tail.log));
+            }
+            poll_sleep(Duration::from_secs(LOGS_POLLING_INTERVAL_SECS), callback)?;
+        }
+    }
+}
+
+/// Write a log kept by `follow_logs` to `path`
+pub(crate) fn save_log(path: &Path, log: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, log).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_filter() {
+        let since = DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut tail = LogTail::new(
+            String::new(),
+            LogFilter {
+                since: Some(since),
+                grep: Some("segment".to_string()),
+            },
+            false,
+        );
+        let mut out = Vec::new();
+        for line in [
+            "2025-06-01T11:59:00Z proving segment 0\n",
+            "[2025-06-01 12:00:01.5] proving segment 1\n",
+            "  continued segment output\n",
+            "2025-06-01T12:00:02Z aggregating\n",
+        ] {
+            tail.write_line(line.as_bytes(), &mut out).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "[2025-06-01 12:00:01.5] proving segment 1\n  continued segment output\n"
+        );
+        assert_eq!(parse_log_timestamp("no timestamp here"), None);
+    }
+
+    #[test]
+    fn test_skip_read() {
+        let mut tail = LogTail::new(String::new(), LogFilter::default(), true);
+        tail.offset = 4;
+        tail.log = Some(b"abcd".to_vec());
+        // A full response to a range request only contributes the unread bytes
+        assert_eq!(&tail.skip_read(Bytes::from_static(b"abcdef"))[..], b"ef");
+        // A shorter log means the job restarted and its log starts over
+        assert_eq!(&tail.skip_read(Bytes::from_static(b"xy"))[..], b"xy");
+        assert_eq!(tail.offset, 0);
+        assert_eq!(tail.log.as_deref(), Some(&b""[..]));
+    }
+}
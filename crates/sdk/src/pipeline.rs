@@ -0,0 +1,214 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use eyre::Result;
+
+use crate::{
+    AxiomSdk, ProgressCallback, ProofType,
+    build::{BuildArgs, BuildSdk, UploadExeArgs},
+    input::Input,
+    prove::{ProveArgs, ProveSdk},
+    run::RunArgs,
+};
+
+pub trait PipelineSdk {
+    /// Upload a program, sanity check it with a `meter` execution, prove it, download the
+    /// proof and verify it. Stops at the first step that fails.
+    fn run_pipeline(&self, program_dir: impl AsRef<Path>, args: PipelineArgs) -> PipelineReport;
+}
+
+#[derive(Debug)]
+pub enum PipelineUpload {
+    /// Upload a locally-built ELF + VMEXE
+    Local(UploadExeArgs),
+    /// Upload the source tree and build it on the Axiom Proving Service
+    Remote(BuildArgs),
+}
+
+#[derive(Debug)]
+pub struct PipelineArgs {
+    pub upload: PipelineUpload,
+    pub input: Option<Input>,
+    pub proof_type: ProofType,
+    pub num_gpus: Option<usize>,
+    pub priority: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineStage {
+    Build,
+    Execute,
+    Prove,
+    Download,
+    Verify,
+}
+
+impl std::fmt::Display for PipelineStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PipelineStage::Build => write!(f, "build"),
+            PipelineStage::Execute => write!(f, "execute"),
+            PipelineStage::Prove => write!(f, "prove"),
+            PipelineStage::Download => write!(f, "download"),
+            PipelineStage::Verify => write!(f, "verify"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PipelineStep {
+    pub stage: PipelineStage,
+    /// ID of the program, execution, proof or verification created by the step
+    pub id: Option<String>,
+    pub elapsed: Duration,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct PipelineReport {
+    /// Steps that were run, in order. Only the last one can have failed.
+    pub steps: Vec<PipelineStep>,
+    /// Where the proof was saved, if it was downloaded
+    pub proof_path: Option<PathBuf>,
+}
+
+impl PipelineReport {
+    pub fn failed_step(&self) -> Option<&PipelineStep> {
+        self.steps.iter().find(|step| step.error.is_some())
+    }
+
+    pub fn total_elapsed(&self) -> Duration {
+        self.steps.iter().map(|step| step.elapsed).sum()
+    }
+
+    /// Run `f` as the next step, recording its ID, duration and error. Returns `None`
+    /// if the step failed.
+    fn run_step<T>(
+        &mut self,
+        stage: PipelineStage,
+        f: impl FnOnce(&mut Option<String>) -> Result<T>,
+    ) -> Option<T> {
+        let start = Instant::now();
+        let mut id = None;
+        let result = f(&mut id);
+        let (value, error) = match result {
+            Ok(value) => (Some(value), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        self.steps.push(PipelineStep {
+            stage,
+            id,
+            elapsed: start.elapsed(),
+            error,
+        });
+        value
+    }
+}
+
+impl PipelineSdk for AxiomSdk {
+    fn run_pipeline(&self, program_dir: impl AsRef<Path>, args: PipelineArgs) -> PipelineReport {
+        self.run_pipeline_base(program_dir, args, &*self.callback)
+    }
+}
+
+impl AxiomSdk {
+    pub fn run_pipeline_base(
+        &self,
+        program_dir: impl AsRef<Path>,
+        args: PipelineArgs,
+        callback: &dyn ProgressCallback,
+    ) -> PipelineReport {
+        let mut report = PipelineReport::default();
+
+        let Some(build_status) = report.run_step(PipelineStage::Build, |id| {
+            let program_id = match args.upload {
+                PipelineUpload::Local(upload_args) => {
+                    self.upload_exe_base(&program_dir, upload_args, callback)?
+                }
+                PipelineUpload::Remote(build_args) => {
+                    self.register_new_program_base(&program_dir, build_args, callback)?
+                }
+            };
+            *id = Some(program_id.clone());
+            self.wait_for_build_completion_base(&program_id, callback)?;
+            self.get_build_status(&program_id)
+        }) else {
+            return report;
+        };
+        let program_id = build_status.id;
+
+        let executed = report.run_step(PipelineStage::Execute, |id| {
+            let execution_id = self.execute_program_base(
+                RunArgs {
+                    program_id: Some(program_id.clone()),
+                    input: args.input.clone(),
+                    mode: "meter".to_string(),
+                },
+                callback,
+            )?;
+            *id = Some(execution_id.clone());
+            self.wait_for_execution_completion_base(&execution_id, callback)
+        });
+        if executed.is_none() {
+            return report;
+        }
+
+        let Some(proof_id) = report.run_step(PipelineStage::Prove, |id| {
+            let proof_id = self.generate_new_proof_base(
+                ProveArgs {
+                    program_id: Some(program_id.clone()),
+                    input: args.input,
+                    proof_type: Some(args.proof_type),
+                    num_gpus: args.num_gpus,
+                    priority: args.priority,
+                    deferred_proofs: Vec::new(),
+                },
+                callback,
+            )?;
+            *id = Some(proof_id.clone());
+            let status = self.wait_for_proof_completion_base(&proof_id, false, callback)?;
+            if status.state != "Succeeded" {
+                eyre::bail!("Proof ended in state {}", status.state);
+            }
+            Ok(proof_id)
+        }) else {
+            return report;
+        };
+
+        let Some(proof_path) = report.run_step(PipelineStage::Download, |id| {
+            *id = Some(proof_id.clone());
+            // Same layout as `prove download`
+            let proof_dir = PathBuf::from("axiom-artifacts")
+                .join(format!("program-{program_id}"))
+                .join("proofs")
+                .join(&proof_id);
+            std::fs::create_dir_all(&proof_dir)?;
+            let proof_path = proof_dir.join(format!("{}-proof.json", args.proof_type));
+            self.get_generated_proof(&proof_id, &args.proof_type, Some(proof_path.clone()))?;
+            Ok(proof_path)
+        }) else {
+            return report;
+        };
+        report.proof_path = Some(proof_path.clone());
+
+        report.run_step(PipelineStage::Verify, |id| {
+            let verify_id = match args.proof_type {
+                ProofType::Evm => {
+                    self.verify_evm_base(Some(&build_status.config_uuid), proof_path, callback)?
+                }
+                ProofType::Stark => self.verify_stark_base(&program_id, proof_path, callback)?,
+            };
+            *id = Some(verify_id.clone());
+            match args.proof_type {
+                ProofType::Evm => self.wait_for_evm_verify_completion_base(&verify_id, callback),
+                ProofType::Stark => {
+                    self.wait_for_stark_verify_completion_base(&verify_id, callback)
+                }
+            }
+        });
+
+        report
+    }
+}
@@ -4,13 +4,20 @@ use bytes::Bytes;
 use eyre::{Context, OptionExt, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
 
 use crate::{
-    AxiomSdk, ProgressCallback, ProofType, authenticated_get, authenticated_post, download_file,
-    input::Input, send_request_json,
+    AxiomSdk, NoopCallback, ProgressCallback, ProofType, authenticated_get, authenticated_post,
+    download_file,
+    input::Input,
+    logs::{LogFilter, save_log},
+    poll_sleep, send_request_json, sha256_file, start_wait,
 };
 
 const PROOF_POLLING_INTERVAL_SECS: u64 = 10;
+const SEARCH_PAGE_SIZE: u32 = 100;
+/// Number of proofs canceled at the same time
+const CANCEL_CONCURRENCY: usize = 16;
 
 pub trait ProveSdk {
     fn list_proofs(
@@ -19,8 +26,27 @@ pub trait ProveSdk {
         page: Option<u32>,
         page_size: Option<u32>,
     ) -> Result<ProofListResponse>;
+    /// List proofs of a program matching the filters and ordering in `query`
+    fn list_proofs_with_query(
+        &self,
+        program_id: &str,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        query: &ProofListQuery,
+    ) -> Result<ProofListResponse>;
+    /// List proofs across all programs of the account, or of a project or program if
+    /// `query` sets one
+    fn search_proofs(
+        &self,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        query: &ProofListQuery,
+    ) -> Result<ProofListResponse>;
     fn get_proof_status(&self, proof_id: &str) -> Result<ProofStatus>;
     fn get_proof_logs(&self, proof_id: &str) -> Result<()>;
+    /// Print the proof's logs as they are written until the proof finishes, and save
+    /// them to its artifacts directory if `save` is set
+    fn follow_proof_logs(&self, proof_id: &str, filter: &LogFilter, save: bool) -> Result<()>;
     fn get_generated_proof(
         &self,
         proof_id: &str,
@@ -29,9 +55,17 @@ pub trait ProveSdk {
     ) -> Result<Bytes>;
     fn save_proof_logs_to_path(&self, proof_id: &str, output_path: PathBuf) -> Result<()>;
     fn generate_new_proof(&self, args: ProveArgs) -> Result<String>;
+    /// Submit a proof again with the input and settings it was submitted with, except
+    /// for any overrides in `args`. Returns the ID of the new proof.
+    fn retry_proof(&self, proof_id: &str, args: RetryArgs) -> Result<String>;
     fn wait_for_proof_completion(&self, proof_id: &str, save: bool) -> Result<ProofStatus>;
     fn cancel_proof(&self, proof_id: &str) -> Result<String>;
     fn wait_for_proof_cancellation(&self, proof_id: &str) -> Result<()>;
+    /// List every proof matching `query`, across all pages
+    fn search_all_proofs(&self, query: &ProofListQuery) -> Result<Vec<ProofStatus>>;
+    /// Cancel several proofs in parallel and wait until each one is canceled. Returns
+    /// the outcome for each proof, in the order they finished.
+    fn cancel_proofs(&self, proof_ids: &[String]) -> Vec<(String, Result<()>)>;
 }
 
 #[derive(Debug)]
@@ -53,6 +87,69 @@ pub struct ProveArgs {
     pub deferred_proofs: Vec<PathBuf>,
 }
 
+/// Settings to change when retrying a proof. Unset fields keep their original value.
+#[derive(Debug, Default)]
+pub struct RetryArgs {
+    pub num_gpus: Option<usize>,
+    pub priority: Option<u8>,
+}
+
+/// What a proof was submitted with, saved as `submission.json` in its artifacts
+/// directory so that it can be retried
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofSubmission {
+    pub program_id: String,
+    pub proof_type: ProofType,
+    pub num_gpus: Option<usize>,
+    pub priority: Option<u8>,
+    /// The input JSON as sent to the API
+    pub input: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deferred_proofs: Vec<DeferredProof>,
+    /// ID of the proof this one is a retry of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_of: Option<String>,
+    /// IDs of the retries of this proof
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retries: Vec<String>,
+}
+
+/// A deferred proof file a proof was submitted with, and the SHA-256 of its contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeferredProof {
+    pub path: PathBuf,
+    pub sha256: String,
+}
+
+impl ProofSubmission {
+    fn path(program_id: &str, proof_id: &str) -> PathBuf {
+        proof_artifacts_dir(program_id, proof_id).join("submission.json")
+    }
+
+    /// Load the submission of a proof saved when it was submitted from this directory
+    pub fn load(program_id: &str, proof_id: &str) -> Result<Self> {
+        let path = Self::path(program_id, proof_id);
+        let contents = fs::read_to_string(&path).with_context(|| {
+            format!(
+                "No submission record for proof {proof_id} at {}. Only proofs submitted from this directory can be retried",
+                path.display()
+            )
+        })?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    fn save(&self, proof_id: &str) -> Result<PathBuf> {
+        let path = Self::path(&self.program_id, proof_id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProofStatus {
     pub id: String,
@@ -70,6 +167,30 @@ pub struct ProofStatus {
     pub priority: u8,
 }
 
+/// Filters and ordering for proof listings. Unset fields are not sent.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ProofListQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    /// RFC3339 timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<String>,
+    /// RFC3339 timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<String>,
+    /// Field to sort by, prefixed with `-` for descending order (e.g. "-created_at")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProofListResponse {
     pub items: Vec<ProofStatus>,
@@ -90,15 +211,38 @@ impl ProveSdk for AxiomSdk {
         program_id: &str,
         page: Option<u32>,
         page_size: Option<u32>,
+    ) -> Result<ProofListResponse> {
+        self.list_proofs_with_query(program_id, page, page_size, &ProofListQuery::default())
+    }
+
+    fn list_proofs_with_query(
+        &self,
+        program_id: &str,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        query: &ProofListQuery,
+    ) -> Result<ProofListResponse> {
+        let query = ProofListQuery {
+            program_id: Some(program_id.to_string()),
+            ..query.clone()
+        };
+        self.search_proofs(page, page_size, &query)
+    }
+
+    fn search_proofs(
+        &self,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        query: &ProofListQuery,
     ) -> Result<ProofListResponse> {
         let page = page.unwrap_or(1);
         let page_size = page_size.unwrap_or(20);
         let url = format!(
-            "{}/proofs?program_id={}&page={}&page_size={}",
-            self.config.api_url, program_id, page, page_size
+            "{}/proofs?page={}&page_size={}",
+            self.config.api_url, page, page_size
         );
 
-        let request = authenticated_get(&self.config, &url)?;
+        let request = authenticated_get(&self.config, &url)?.query(query);
         send_request_json(request, "Failed to list proofs")
     }
 
@@ -138,6 +282,10 @@ impl ProveSdk for AxiomSdk {
         Ok(())
     }
 
+    fn follow_proof_logs(&self, proof_id: &str, filter: &LogFilter, save: bool) -> Result<()> {
+        self.follow_proof_logs_base(proof_id, filter, save, &*self.callback)
+    }
+
     fn get_generated_proof(
         &self,
         proof_id: &str,
@@ -168,6 +316,10 @@ impl ProveSdk for AxiomSdk {
         self.generate_new_proof_base(args, &*self.callback)
     }
 
+    fn retry_proof(&self, proof_id: &str, args: RetryArgs) -> Result<String> {
+        self.retry_proof_base(proof_id, args, &*self.callback)
+    }
+
     fn wait_for_proof_completion(&self, proof_id: &str, save: bool) -> Result<ProofStatus> {
         self.wait_for_proof_completion_base(proof_id, save, &*self.callback)
     }
@@ -202,13 +354,158 @@ impl ProveSdk for AxiomSdk {
     fn wait_for_proof_cancellation(&self, proof_id: &str) -> Result<()> {
         self.wait_for_proof_cancellation_base(proof_id, &*self.callback)
     }
+
+    fn search_all_proofs(&self, query: &ProofListQuery) -> Result<Vec<ProofStatus>> {
+        let mut proofs = Vec::new();
+        let mut page = 1;
+        loop {
+            let response = self.search_proofs(Some(page), Some(SEARCH_PAGE_SIZE), query)?;
+            proofs.extend(response.items);
+            if page >= response.pagination.pages {
+                return Ok(proofs);
+            }
+            page += 1;
+        }
+    }
+
+    fn cancel_proofs(&self, proof_ids: &[String]) -> Vec<(String, Result<()>)> {
+        self.cancel_proofs_base(proof_ids, &*self.callback)
+    }
 }
 
 impl AxiomSdk {
+    pub fn cancel_proofs_base(
+        &self,
+        proof_ids: &[String],
+        callback: &dyn ProgressCallback,
+    ) -> Vec<(String, Result<()>)> {
+        let total = proof_ids.len();
+        let mut results = Vec::with_capacity(total);
+        for chunk in proof_ids.chunks(CANCEL_CONCURRENCY) {
+            let (sender, receiver) = std::sync::mpsc::channel();
+            std::thread::scope(|scope| {
+                for proof_id in chunk {
+                    let sender = sender.clone();
+                    let config = self.config.clone();
+                    scope.spawn(move || {
+                        // The SDK is not shareable across threads, so each one gets its own
+                        let sdk = AxiomSdk::new(config);
+                        let result = sdk.cancel_proof(proof_id).and_then(|_| {
+                            sdk.wait_for_proof_cancellation_base(proof_id, &NoopCallback)
+                        });
+                        let _ = sender.send((proof_id.clone(), result));
+                    });
+                }
+                drop(sender);
+                for (proof_id, result) in receiver {
+                    let done = results.len() + 1;
+                    match &result {
+                        Ok(()) => {
+                            callback.on_success(&format!("[{done}/{total}] {proof_id} canceled"))
+                        }
+                        Err(e) => callback.on_error(&format!(
+                            "[{done}/{total}] {proof_id} could not be canceled: {e}"
+                        )),
+                    }
+                    results.push((proof_id, result));
+                }
+            });
+        }
+        results
+    }
+
+    pub fn follow_proof_logs_base(
+        &self,
+        proof_id: &str,
+        filter: &LogFilter,
+        save: bool,
+        callback: &dyn ProgressCallback,
+    ) -> Result<()> {
+        let url = format!("{}/proofs/{}/logs", self.config.api_url, proof_id);
+        let (proof_status, log) = self.follow_logs(url, filter, save, callback, || {
+            let proof_status = self.get_proof_status(proof_id)?;
+            let finished = matches!(
+                proof_status.state.as_str(),
+                "Succeeded" | "Failed" | "Canceled"
+            );
+            Ok(finished.then_some(proof_status))
+        })?;
+
+        if let Some(log) = log {
+            let logs_path =
+                proof_artifacts_dir(&proof_status.program_uuid, proof_id).join("logs.txt");
+            save_log(&logs_path, &log)?;
+            callback.on_success(&format!(
+                "Proof {}, logs saved to {}",
+                proof_status.state,
+                logs_path.display()
+            ));
+        } else {
+            callback.on_info(&format!("Proof {}", proof_status.state));
+        }
+        Ok(())
+    }
+
     pub fn generate_new_proof_base(
         &self,
         args: ProveArgs,
         callback: &dyn ProgressCallback,
+    ) -> Result<String> {
+        self.submit_proof(args, None, callback)
+    }
+
+    pub fn retry_proof_base(
+        &self,
+        proof_id: &str,
+        args: RetryArgs,
+        callback: &dyn ProgressCallback,
+    ) -> Result<String> {
+        let proof_status = self.get_proof_status(proof_id)?;
+        let mut original = ProofSubmission::load(&proof_status.program_uuid, proof_id)?;
+        for deferred in &original.deferred_proofs {
+            let sha256 = sha256_file(&deferred.path)?;
+            if sha256 != deferred.sha256 {
+                eyre::bail!(
+                    "Deferred proof {} changed since proof {proof_id} was submitted (SHA-256 {sha256}, was {}). Submit a new proof with `cargo axiom prove` instead",
+                    deferred.path.display(),
+                    deferred.sha256
+                );
+            }
+        }
+        callback.on_info(&format!(
+            "Retrying proof {proof_id} ({})",
+            proof_status.state
+        ));
+
+        // The status has the values the service used, including its defaults for
+        // options the original submission left out
+        let prove_args = ProveArgs {
+            program_id: Some(original.program_id.clone()),
+            input: Some(Input::Value(original.input.clone())),
+            proof_type: Some(original.proof_type),
+            num_gpus: Some(args.num_gpus.unwrap_or(proof_status.num_gpus)),
+            priority: Some(args.priority.unwrap_or(proof_status.priority)),
+            deferred_proofs: original
+                .deferred_proofs
+                .iter()
+                .map(|deferred| deferred.path.clone())
+                .collect(),
+        };
+        let new_proof_id = self.submit_proof(prove_args, Some(proof_id), callback)?;
+
+        original.retries.push(new_proof_id.clone());
+        if let Err(e) = original.save(proof_id) {
+            callback.on_warning(&format!("Failed to link retry to proof {proof_id}: {e}"));
+        }
+        Ok(new_proof_id)
+    }
+
+    /// Submit a proof and save what it was submitted with to its artifacts directory
+    fn submit_proof(
+        &self,
+        args: ProveArgs,
+        retry_of: Option<&str>,
+        callback: &dyn ProgressCallback,
     ) -> Result<String> {
         // Get the program_id from args, return error if not provided
         let program_id = args
@@ -254,6 +551,7 @@ impl AxiomSdk {
         // endpoint: the `input` form field carries the JSON body string, and
         // each child proof is a `child_proofs` file part (part order =
         // circuit packing order).
+        let mut deferred_proofs = Vec::new();
         let request = if args.deferred_proofs.is_empty() {
             authenticated_post(&self.config, &url)?
                 .header("Content-Type", "application/json")
@@ -278,6 +576,10 @@ impl AxiomSdk {
                         path.display()
                     );
                 }
+                deferred_proofs.push(DeferredProof {
+                    path: fs::canonicalize(path).unwrap_or_else(|_| path.clone()),
+                    sha256: hex::encode(Sha256::digest(&bytes)),
+                });
                 let file_name = path
                     .file_name()
                     .map(|n| n.to_string_lossy().into_owned())
@@ -294,6 +596,21 @@ impl AxiomSdk {
         let proof_id = response_json["id"].as_str().unwrap();
 
         callback.on_success(&format!("Proof generation initiated ({})", proof_id));
+
+        let submission = ProofSubmission {
+            program_id,
+            proof_type,
+            num_gpus: args.num_gpus,
+            priority: args.priority,
+            input: body,
+            deferred_proofs,
+            retry_of: retry_of.map(str::to_string),
+            retries: Vec::new(),
+        };
+        // The proof was submitted, so failing to record it is not an error
+        if let Err(e) = submission.save(proof_id) {
+            callback.on_warning(&format!("Failed to save proof submission: {e}"));
+        }
         Ok(proof_id.to_string())
     }
 
@@ -303,6 +620,7 @@ impl AxiomSdk {
         save: bool,
         callback: &dyn ProgressCallback,
     ) -> Result<ProofStatus> {
+        let _wait = start_wait();
         use std::time::Duration;
 
         let mut spinner_started = false;
@@ -409,12 +727,27 @@ impl AxiomSdk {
                         }
                     }
 
+                    self.hooks.run(
+                        "proof",
+                        proof_id,
+                        &proof_status.state,
+                        &proof_status,
+                        callback,
+                    );
                     return Ok(proof_status);
                 }
                 "Failed" => {
                     if spinner_started {
                         callback.on_progress_finish("");
                     }
+                    self.hooks.run(
+                        "proof",
+                        proof_id,
+                        &proof_status.state,
+                        &proof_status,
+                        callback,
+                    );
+                    self.explain_proof_failure(&proof_status, callback);
                     let error_msg = proof_status
                         .error_message
                         .unwrap_or_else(|| "Unknown error".to_string());
@@ -426,6 +759,13 @@ impl AxiomSdk {
                     } else {
                         callback.on_info("Proof generation was canceled");
                     }
+                    self.hooks.run(
+                        "proof",
+                        proof_id,
+                        &proof_status.state,
+                        &proof_status,
+                        callback,
+                    );
                     return Ok(proof_status);
                 }
                 "Canceling" => {
@@ -435,14 +775,14 @@ impl AxiomSdk {
                     } else {
                         callback.on_progress_update_message("Canceling proof");
                     }
-                    std::thread::sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS));
+                    poll_sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS), callback)?;
                 }
                 "Queued" => {
                     if !spinner_started {
                         callback.on_spinner_start("Proof queued");
                         spinner_started = true;
                     }
-                    std::thread::sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS));
+                    poll_sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS), callback)?;
                 }
                 "InProgress" => {
                     if !spinner_started {
@@ -452,7 +792,7 @@ impl AxiomSdk {
                         // Update message if we were previously in queued state
                         callback.on_progress_update_message("Generating proof");
                     }
-                    std::thread::sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS));
+                    poll_sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS), callback)?;
                 }
                 _ => {
                     let status_message = format!("Proof status: {}", proof_status.state);
@@ -462,7 +802,7 @@ impl AxiomSdk {
                     } else {
                         callback.on_progress_update_message(&status_message);
                     }
-                    std::thread::sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS));
+                    poll_sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS), callback)?;
                 }
             }
         }
@@ -473,6 +813,7 @@ impl AxiomSdk {
         proof_id: &str,
         callback: &dyn ProgressCallback,
     ) -> Result<()> {
+        let _wait = start_wait();
         use std::time::Duration;
 
         let mut spinner_started = false;
@@ -499,7 +840,7 @@ impl AxiomSdk {
                         callback.on_spinner_start("Canceling proof");
                         spinner_started = true;
                     }
-                    std::thread::sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS));
+                    poll_sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS), callback)?;
                 }
                 "Failed" => {
                     if spinner_started {
@@ -527,7 +868,7 @@ impl AxiomSdk {
                         callback.on_spinner_start("Waiting for cancellation");
                         spinner_started = true;
                     }
-                    std::thread::sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS));
+                    poll_sleep(Duration::from_secs(PROOF_POLLING_INTERVAL_SECS), callback)?;
                 }
             }
         }
@@ -544,6 +885,14 @@ fn looks_like_json(bytes: &[u8]) -> bool {
         .is_some_and(|b| *b == b'{')
 }
 
+/// Directory the artifacts of a proof are saved to
+pub fn proof_artifacts_dir(program_id: &str, proof_id: &str) -> PathBuf {
+    PathBuf::from("axiom-artifacts")
+        .join(format!("program-{program_id}"))
+        .join("proofs")
+        .join(proof_id)
+}
+
 #[cfg(test)]
 mod deferred_proof_tests {
     use super::looks_like_json;
@@ -0,0 +1,255 @@
+//! Decoding the user public values revealed by a program, from proofs and executions.
+
+use std::{path::Path, str::FromStr};
+
+use eyre::{Context, OptionExt, Result};
+use serde_json::Value;
+
+use crate::{AxiomSdk, run::RunSdk};
+
+pub trait PublicValuesSdk {
+    /// Public values revealed by a finished execution
+    fn get_execution_public_values(&self, execution_id: &str) -> Result<Vec<u8>>;
+}
+
+impl PublicValuesSdk for AxiomSdk {
+    fn get_execution_public_values(&self, execution_id: &str) -> Result<Vec<u8>> {
+        let status = self.get_execution_status(execution_id)?;
+        match &status.public_values {
+            Some(public_values) if !public_values.is_null() => parse_public_values(public_values),
+            _ => eyre::bail!(
+                "Execution {execution_id} has no public values (status: {})",
+                status.status
+            ),
+        }
+    }
+}
+
+/// Public values of a downloaded proof file, from its `user_public_values` field
+pub fn read_proof_public_values(path: &Path) -> Result<Vec<u8>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read proof file {}", path.display()))?;
+    let proof: Value = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid JSON in proof file {}", path.display()))?;
+    let public_values = proof
+        .get("user_public_values")
+        .ok_or_eyre("Missing 'user_public_values' field in proof")?;
+    parse_public_values(public_values)
+}
+
+/// Public values given as a hex string, an array of hex strings, or an array of bytes
+pub fn parse_public_values(value: &Value) -> Result<Vec<u8>> {
+    match value {
+        Value::String(s) => decode_hex(s),
+        Value::Array(items) => {
+            let mut bytes = Vec::new();
+            for item in items {
+                match item {
+                    Value::String(s) => bytes.extend(decode_hex(s)?),
+                    Value::Number(n) => {
+                        let byte = n
+                            .as_u64()
+                            .and_then(|n| u8::try_from(n).ok())
+                            .ok_or_else(|| eyre::eyre!("Public value {n} is not a byte"))?;
+                        bytes.push(byte);
+                    }
+                    _ => eyre::bail!("Unsupported public value: {item}"),
+                }
+            }
+            Ok(bytes)
+        }
+        _ => eyre::bail!("Unsupported public values: {value}"),
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.trim();
+    hex::decode(s.strip_prefix("0x").unwrap_or(s)).context("Public values are not valid hex")
+}
+
+/// The values as little-endian u32 words. A last partial word is padded with zeros.
+pub fn u32_words(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks(4)
+        .map(|chunk| {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(word)
+        })
+        .collect()
+}
+
+/// `0x`-prefixed hex encoding of bytes
+pub fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// The values as 32-byte words, hex-encoded. A last partial word is shown as is.
+pub fn words32(bytes: &[u8]) -> Vec<String> {
+    bytes.chunks(32).map(to_hex).collect()
+}
+
+/// The values as UTF-8 text without trailing zero padding, if they are valid UTF-8
+pub fn utf8(bytes: &[u8]) -> Option<&str> {
+    let end = bytes.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    std::str::from_utf8(&bytes[..end]).ok()
+}
+
+/// A field of a public values schema such as `u64,bytes32,address`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    Bool,
+    /// Unsigned integer of this many bits
+    Uint(usize),
+    /// Signed integer of this many bits
+    Int(usize),
+    /// 20-byte Ethereum address
+    Address,
+    /// Fixed-size byte string of this many bytes
+    Bytes(usize),
+}
+
+impl FieldType {
+    pub fn size(&self) -> usize {
+        match self {
+            FieldType::Bool => 1,
+            FieldType::Uint(bits) | FieldType::Int(bits) => bits / 8,
+            FieldType::Address => 20,
+            FieldType::Bytes(size) => *size,
+        }
+    }
+
+    /// Render the field's bytes. Integers are little-endian, like values revealed
+    /// with OpenVM's `reveal_u32`.
+    fn render(&self, bytes: &[u8]) -> String {
+        match self {
+            FieldType::Bool => (bytes[0] != 0).to_string(),
+            FieldType::Uint(_) => {
+                let mut word = [0u8; 16];
+                word[..bytes.len()].copy_from_slice(bytes);
+                u128::from_le_bytes(word).to_string()
+            }
+            FieldType::Int(_) => {
+                // Sign-extend before reading as i128
+                let fill = if bytes[bytes.len() - 1] & 0x80 != 0 {
+                    0xff
+                } else {
+                    0
+                };
+                let mut word = [fill; 16];
+                word[..bytes.len()].copy_from_slice(bytes);
+                i128::from_le_bytes(word).to_string()
+            }
+            FieldType::Address | FieldType::Bytes(_) => to_hex(bytes),
+        }
+    }
+}
+
+impl std::fmt::Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldType::Bool => write!(f, "bool"),
+            FieldType::Uint(bits) => write!(f, "u{bits}"),
+            FieldType::Int(bits) => write!(f, "i{bits}"),
+            FieldType::Address => write!(f, "address"),
+            FieldType::Bytes(size) => write!(f, "bytes{size}"),
+        }
+    }
+}
+
+impl FromStr for FieldType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let int_bits = |bits: &str| match bits.parse() {
+            Ok(bits @ (8 | 16 | 32 | 64 | 128)) => Ok(bits),
+            _ => Err(format!("unsupported integer type '{s}'")),
+        };
+        match s.trim() {
+            "bool" => Ok(FieldType::Bool),
+            "address" => Ok(FieldType::Address),
+            t if t.starts_with('u') => int_bits(&t[1..]).map(FieldType::Uint),
+            t if t.starts_with('i') => int_bits(&t[1..]).map(FieldType::Int),
+            t if t.starts_with("bytes") => match t["bytes".len()..].parse() {
+                Ok(size @ 1..=32) => Ok(FieldType::Bytes(size)),
+                _ => Err(format!("'{s}' must be bytes1 to bytes32")),
+            },
+            _ => Err(format!(
+                "unknown type '{s}', expected bool, u8-u128, i8-i128, address or bytes1-bytes32"
+            )),
+        }
+    }
+}
+
+/// Parse a comma-separated schema such as `u64,bytes32,address`
+pub fn parse_schema(schema: &str) -> Result<Vec<FieldType>, String> {
+    schema.split(',').map(str::parse).collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TypedValue {
+    pub field_type: FieldType,
+    /// Offset of the field in the public values
+    pub offset: usize,
+    pub value: String,
+}
+
+/// Decode the public values as consecutive fields of `schema`. Returns the values and
+/// the number of bytes left over after the last field.
+pub fn decode_with_schema(bytes: &[u8], schema: &[FieldType]) -> Result<(Vec<TypedValue>, usize)> {
+    let mut offset = 0;
+    let mut values = Vec::with_capacity(schema.len());
+    for field_type in schema {
+        let end = offset + field_type.size();
+        let Some(field) = bytes.get(offset..end) else {
+            eyre::bail!(
+                "The schema needs {} bytes for {field_type} at offset {offset}, but the public values are only {} bytes",
+                field_type.size(),
+                bytes.len()
+            );
+        };
+        values.push(TypedValue {
+            field_type: *field_type,
+            offset,
+            value: field_type.render(field),
+        });
+        offset = end;
+    }
+    Ok((values, bytes.len() - offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_public_values() {
+        assert_eq!(
+            parse_public_values(&json!("0x2a000000ff")).unwrap(),
+            vec![42, 0, 0, 0, 255]
+        );
+        assert_eq!(
+            parse_public_values(&json!([1, 2, 3])).unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(parse_public_values(&json!([256])).is_err());
+
+        let bytes = parse_public_values(&json!("2a000000ff")).unwrap();
+        assert_eq!(u32_words(&bytes), vec![42, 255]);
+        assert_eq!(utf8(b"hi\0\0"), Some("hi"));
+
+        let schema = parse_schema("u32,i8").unwrap();
+        let (values, left_over) = decode_with_schema(&bytes, &schema).unwrap();
+        assert_eq!(values[0].value, "42");
+        assert_eq!(values[1].value, "-1");
+        assert_eq!(left_over, 0);
+        assert!(decode_with_schema(&bytes, &[FieldType::Uint(64)]).is_err());
+        assert!(parse_schema("u24").is_err());
+        assert_eq!(
+            parse_schema("bytes32,address").unwrap()[0],
+            FieldType::Bytes(32)
+        );
+    }
+}
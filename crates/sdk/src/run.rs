@@ -3,7 +3,12 @@ use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
-use crate::{API_KEY_HEADER, AxiomSdk, ProgressCallback, add_cli_version_header, input::Input};
+use crate::{
+    API_KEY_HEADER, AxiomSdk, ClientError, ProgressCallback, add_cli_version_header,
+    input::Input,
+    logs::{LogFilter, save_log},
+    poll_sleep, start_wait,
+};
 
 const EXECUTION_POLLING_INTERVAL_SECS: u64 = 10;
 
@@ -18,7 +23,31 @@ pub trait RunSdk {
         page: Option<u32>,
         page_size: Option<u32>,
     ) -> Result<ExecutionListResponse>;
+    /// List executions of a program matching the filters and ordering in `query`
+    fn list_executions_with_query(
+        &self,
+        program_id: &str,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        query: &ExecutionListQuery,
+    ) -> Result<ExecutionListResponse>;
+    /// List executions across all programs of the account, or of a project or program if
+    /// `query` sets one
+    fn search_executions(
+        &self,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        query: &ExecutionListQuery,
+    ) -> Result<ExecutionListResponse>;
     fn get_execution_logs(&self, execution_id: &str) -> Result<()>;
+    /// Print the execution's logs as they are written until the execution finishes, and
+    /// save them to its artifacts directory if `save` is set
+    fn follow_execution_logs(
+        &self,
+        execution_id: &str,
+        filter: &LogFilter,
+        save: bool,
+    ) -> Result<()>;
 }
 
 #[derive(Debug)]
@@ -56,6 +85,30 @@ pub struct ExecutionStatus {
     pub total_tick: Option<u64>,
 }
 
+/// Filters and ordering for execution listings. Unset fields are not sent.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ExecutionListQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    /// RFC3339 timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<String>,
+    /// RFC3339 timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<String>,
+    /// Field to sort by, prefixed with `-` for descending order (e.g. "-created_at")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExecutionListResponse {
     pub items: Vec<ExecutionStatus>,
@@ -85,13 +138,8 @@ impl RunSdk for AxiomSdk {
             let execution_status = serde_json::from_value(body)?;
             Ok(execution_status)
         } else if response.status().is_client_error() {
-            let status = response.status();
-            let error_text = response.text()?;
-            Err(eyre::eyre!(
-                "Cannot check execution status: {} (status: {})",
-                error_text,
-                status
-            ))
+            Err(eyre::Report::new(ClientError::from_response(response)?)
+                .wrap_err("Cannot check execution status"))
         } else {
             Err(eyre::eyre!(
                 "Status request failed with status: {}",
@@ -146,17 +194,41 @@ impl RunSdk for AxiomSdk {
         program_id: &str,
         page: Option<u32>,
         page_size: Option<u32>,
+    ) -> Result<ExecutionListResponse> {
+        self.list_executions_with_query(program_id, page, page_size, &ExecutionListQuery::default())
+    }
+
+    fn list_executions_with_query(
+        &self,
+        program_id: &str,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        query: &ExecutionListQuery,
+    ) -> Result<ExecutionListResponse> {
+        let query = ExecutionListQuery {
+            program_id: Some(program_id.to_string()),
+            ..query.clone()
+        };
+        self.search_executions(page, page_size, &query)
+    }
+
+    fn search_executions(
+        &self,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        query: &ExecutionListQuery,
     ) -> Result<ExecutionListResponse> {
         let page = page.unwrap_or(1);
         let page_size = page_size.unwrap_or(20);
         let url = format!(
-            "{}/executions?program_id={}&page={}&page_size={}",
-            self.config.api_url, program_id, page, page_size
+            "{}/executions?page={}&page_size={}",
+            self.config.api_url, page, page_size
         );
         let client = Client::new();
         let api_key = self.config.api_key.as_ref().ok_or_eyre("API key not set")?;
 
         let response = add_cli_version_header(client.get(url).header(API_KEY_HEADER, api_key))
+            .query(query)
             .send()
             .context("Failed to send list request")?;
 
@@ -183,14 +255,7 @@ impl RunSdk for AxiomSdk {
         let url = format!("{}/executions/{}/logs", self.config.api_url, execution_id);
         let request = crate::authenticated_get(&self.config, &url)?;
 
-        let execution_dir =
-            std::path::PathBuf::from("axiom-artifacts").join(format!("execution-{}", execution_id));
-        std::fs::create_dir_all(&execution_dir).context(format!(
-            "Failed to create execution directory: {}",
-            execution_dir.display()
-        ))?;
-
-        let filename = execution_dir.join("logs.txt");
+        let filename = execution_logs_path(execution_id);
         crate::download_file_streaming(
             request,
             filename.clone(),
@@ -200,9 +265,46 @@ impl RunSdk for AxiomSdk {
             .on_success(&format!("✓ {}", filename.display()));
         Ok(())
     }
+
+    fn follow_execution_logs(
+        &self,
+        execution_id: &str,
+        filter: &LogFilter,
+        save: bool,
+    ) -> Result<()> {
+        self.follow_execution_logs_base(execution_id, filter, save, &*self.callback)
+    }
 }
 
 impl AxiomSdk {
+    pub fn follow_execution_logs_base(
+        &self,
+        execution_id: &str,
+        filter: &LogFilter,
+        save: bool,
+        callback: &dyn ProgressCallback,
+    ) -> Result<()> {
+        let url = format!("{}/executions/{}/logs", self.config.api_url, execution_id);
+        let (status, log) = self.follow_logs(url, filter, save, callback, || {
+            let execution_status = self.get_execution_status(execution_id)?;
+            let finished = matches!(execution_status.status.as_str(), "Succeeded" | "Failed");
+            Ok(finished.then_some(execution_status.status))
+        })?;
+
+        if let Some(log) = log {
+            let logs_path = execution_logs_path(execution_id);
+            save_log(&logs_path, &log)?;
+            callback.on_success(&format!(
+                "Execution {}, logs saved to {}",
+                status,
+                logs_path.display()
+            ));
+        } else {
+            callback.on_info(&format!("Execution {status}"));
+        }
+        Ok(())
+    }
+
     pub fn execute_program_base(
         &self,
         args: RunArgs,
@@ -274,6 +376,7 @@ impl AxiomSdk {
         execution_id: &str,
         callback: &dyn ProgressCallback,
     ) -> Result<()> {
+        let _wait = start_wait();
         use std::time::Duration;
 
         let mut spinner_started = false;
@@ -382,12 +485,26 @@ impl AxiomSdk {
                         callback.on_success(&results_path);
                     }
 
+                    self.hooks.run(
+                        "execution",
+                        execution_id,
+                        &execution_status.status,
+                        &execution_status,
+                        callback,
+                    );
                     return Ok(());
                 }
                 "Failed" => {
                     if spinner_started {
                         callback.on_progress_finish("");
                     }
+                    self.hooks.run(
+                        "execution",
+                        execution_id,
+                        &execution_status.status,
+                        &execution_status,
+                        callback,
+                    );
                     let error_msg = execution_status
                         .error_message
                         .unwrap_or_else(|| "Unknown error".to_string());
@@ -398,7 +515,10 @@ impl AxiomSdk {
                         callback.on_spinner_start("Execution queued");
                         spinner_started = true;
                     }
-                    std::thread::sleep(Duration::from_secs(EXECUTION_POLLING_INTERVAL_SECS));
+                    poll_sleep(
+                        Duration::from_secs(EXECUTION_POLLING_INTERVAL_SECS),
+                        callback,
+                    )?;
                 }
                 "InProgress" => {
                     if !spinner_started {
@@ -408,7 +528,10 @@ impl AxiomSdk {
                         // Update message if we were previously in queued state
                         callback.on_progress_update_message("Executing program");
                     }
-                    std::thread::sleep(Duration::from_secs(EXECUTION_POLLING_INTERVAL_SECS));
+                    poll_sleep(
+                        Duration::from_secs(EXECUTION_POLLING_INTERVAL_SECS),
+                        callback,
+                    )?;
                 }
                 _ => {
                     let status_message = format!("Execution status: {}", execution_status.status);
@@ -418,7 +541,10 @@ impl AxiomSdk {
                     } else {
                         callback.on_progress_update_message(&status_message);
                     }
-                    std::thread::sleep(Duration::from_secs(EXECUTION_POLLING_INTERVAL_SECS));
+                    poll_sleep(
+                        Duration::from_secs(EXECUTION_POLLING_INTERVAL_SECS),
+                        callback,
+                    )?;
                 }
             }
         }
@@ -460,3 +586,9 @@ impl AxiomSdk {
         None
     }
 }
+
+fn execution_logs_path(execution_id: &str) -> std::path::PathBuf {
+    std::path::PathBuf::from("axiom-artifacts")
+        .join(format!("execution-{}", execution_id))
+        .join("logs.txt")
+}
@@ -0,0 +1,271 @@
+//! Queue and run durations of jobs, and statistics over the proof history of a program.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{DateTime, Utc};
+use eyre::Result;
+
+use crate::{
+    AxiomSdk,
+    build::BuildStatus,
+    prove::{ProofListQuery, ProofStatus, ProveSdk},
+    run::ExecutionStatus,
+    verify::VerifyStatus,
+};
+
+const STATS_PAGE_SIZE: u32 = 100;
+
+pub trait TimelineSdk {
+    /// Queue and proving time percentiles of the most recent `max_proofs` successful
+    /// proofs of a program, grouped by number of GPUs and priority
+    fn proof_time_stats(
+        &self,
+        program_id: &str,
+        proof_type: Option<String>,
+        max_proofs: u32,
+    ) -> Result<Vec<ProofTimeStats>>;
+}
+
+/// When a job was submitted, launched and finished
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    pub created_at: String,
+    pub launched_at: Option<String>,
+    pub terminated_at: Option<String>,
+}
+
+impl Timeline {
+    /// Time spent waiting to be launched, up to now if the job is still queued
+    pub fn queue_time(&self) -> Option<Duration> {
+        self.durations_at(Utc::now()).0
+    }
+
+    /// Time spent running, up to now if the job is still running
+    pub fn run_time(&self) -> Option<Duration> {
+        self.durations_at(Utc::now()).1
+    }
+
+    /// Time between launch and termination, once the job has finished
+    pub fn finished_run_time(&self) -> Option<Duration> {
+        elapsed(
+            parse_timestamp(self.launched_at.as_deref()),
+            parse_timestamp(self.terminated_at.as_deref()),
+        )
+    }
+
+    /// Time since submission, up to now if the job has not finished yet
+    pub fn total_time(&self) -> Option<Duration> {
+        let end = match &self.terminated_at {
+            Some(terminated_at) => parse_timestamp(Some(terminated_at))?,
+            None => Utc::now(),
+        };
+        elapsed(parse_timestamp(Some(&self.created_at)), Some(end))
+    }
+
+    /// Whether the job has not finished yet
+    pub fn is_ongoing(&self) -> bool {
+        self.terminated_at.is_none()
+    }
+
+    fn durations_at(&self, now: DateTime<Utc>) -> (Option<Duration>, Option<Duration>) {
+        let created_at = parse_timestamp(Some(&self.created_at));
+        let launched_at = parse_timestamp(self.launched_at.as_deref());
+        let terminated_at = parse_timestamp(self.terminated_at.as_deref());
+        let end = terminated_at.unwrap_or(now);
+        // A job canceled before it launched spent all of its time in the queue
+        let queue_time = elapsed(created_at, launched_at.or(Some(end)));
+        let run_time = launched_at.and_then(|launched_at| elapsed(Some(launched_at), Some(end)));
+        (queue_time, run_time)
+    }
+}
+
+impl From<&ProofStatus> for Timeline {
+    fn from(status: &ProofStatus) -> Self {
+        Timeline {
+            created_at: status.created_at.clone(),
+            launched_at: status.launched_at.clone(),
+            terminated_at: status.terminated_at.clone(),
+        }
+    }
+}
+
+impl From<&ExecutionStatus> for Timeline {
+    fn from(status: &ExecutionStatus) -> Self {
+        Timeline {
+            created_at: status.created_at.clone(),
+            launched_at: status.launched_at.clone(),
+            terminated_at: status.terminated_at.clone(),
+        }
+    }
+}
+
+impl From<&BuildStatus> for Timeline {
+    fn from(status: &BuildStatus) -> Self {
+        Timeline {
+            created_at: status.created_at.clone(),
+            launched_at: status.launched_at.clone(),
+            terminated_at: status.terminated_at.clone(),
+        }
+    }
+}
+
+/// Verifications only report when they were submitted
+impl From<&VerifyStatus> for Timeline {
+    fn from(status: &VerifyStatus) -> Self {
+        Timeline {
+            created_at: status.created_at.clone(),
+            launched_at: None,
+            terminated_at: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ProofTimeStats {
+    pub num_gpus: usize,
+    pub priority: u8,
+    pub count: usize,
+    pub queue_p50: Option<Duration>,
+    pub queue_p95: Option<Duration>,
+    pub run_p50: Option<Duration>,
+    pub run_p95: Option<Duration>,
+}
+
+impl TimelineSdk for AxiomSdk {
+    fn proof_time_stats(
+        &self,
+        program_id: &str,
+        proof_type: Option<String>,
+        max_proofs: u32,
+    ) -> Result<Vec<ProofTimeStats>> {
+        let query = ProofListQuery {
+            state: Some("Succeeded".to_string()),
+            proof_type,
+            sort: Some("-created_at".to_string()),
+            ..Default::default()
+        };
+        let mut proofs = Vec::new();
+        let mut page = 1;
+        while proofs.len() < max_proofs as usize {
+            let response =
+                self.list_proofs_with_query(program_id, Some(page), Some(STATS_PAGE_SIZE), &query)?;
+            proofs.extend(response.items);
+            if page >= response.pagination.pages {
+                break;
+            }
+            page += 1;
+        }
+        proofs.truncate(max_proofs as usize);
+        Ok(proof_time_stats(&proofs))
+    }
+}
+
+/// Group finished proofs by number of GPUs and priority, and compute the percentiles of
+/// their queue and proving times
+pub fn proof_time_stats(proofs: &[ProofStatus]) -> Vec<ProofTimeStats> {
+    let mut groups: BTreeMap<(usize, u8), (Vec<Duration>, Vec<Duration>)> = BTreeMap::new();
+    for proof in proofs.iter().filter(|proof| proof.terminated_at.is_some()) {
+        let (queue_times, run_times) = groups.entry((proof.num_gpus, proof.priority)).or_default();
+        let timeline = Timeline::from(proof);
+        queue_times.extend(timeline.queue_time());
+        run_times.extend(timeline.run_time());
+    }
+    groups
+        .into_iter()
+        .map(|((num_gpus, priority), (mut queue_times, mut run_times))| {
+            queue_times.sort();
+            run_times.sort();
+            ProofTimeStats {
+                num_gpus,
+                priority,
+                count: queue_times.len().max(run_times.len()),
+                queue_p50: percentile(&queue_times, 50),
+                queue_p95: percentile(&queue_times, 95),
+                run_p50: percentile(&run_times, 50),
+                run_p95: percentile(&run_times, 95),
+            }
+        })
+        .collect()
+}
+
+/// Nearest-rank percentile of sorted durations
+fn percentile(sorted: &[Duration], percent: usize) -> Option<Duration> {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+fn parse_timestamp(timestamp: Option<&str>) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp?)
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+fn elapsed(start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> Option<Duration> {
+    (end? - start?).to_std().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof(num_gpus: usize, queue_secs: u32, run_secs: u32) -> ProofStatus {
+        let at = |secs: u32| format!("2025-01-01T00:{:02}:{:02}Z", secs / 60, secs % 60);
+        ProofStatus {
+            id: "prf".to_string(),
+            created_at: at(0),
+            state: "Succeeded".to_string(),
+            proof_type: "stark".to_string(),
+            program_uuid: "prg".to_string(),
+            error_message: None,
+            launched_at: Some(at(queue_secs)),
+            terminated_at: Some(at(queue_secs + run_secs)),
+            created_by: "test@example.com".to_string(),
+            cells_used: 1000,
+            num_instructions: None,
+            num_gpus,
+            priority: 5,
+        }
+    }
+
+    #[test]
+    fn test_proof_time_stats() {
+        let mut proofs: Vec<_> = (1..=20).map(|i| proof(1, i, 10 * i)).collect();
+        proofs.push(proof(4, 5, 30));
+        let stats = proof_time_stats(&proofs);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].count, 20);
+        assert_eq!(stats[0].queue_p50, Some(Duration::from_secs(10)));
+        assert_eq!(stats[0].queue_p95, Some(Duration::from_secs(19)));
+        assert_eq!(stats[0].run_p95, Some(Duration::from_secs(190)));
+        assert_eq!(stats[1].num_gpus, 4);
+        assert_eq!(stats[1].run_p50, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_timeline_durations() {
+        let now = DateTime::parse_from_rfc3339("2025-01-01T00:10:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut timeline = Timeline {
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            launched_at: None,
+            terminated_at: None,
+        };
+        assert_eq!(
+            timeline.durations_at(now),
+            (Some(Duration::from_secs(600)), None)
+        );
+        timeline.launched_at = Some("2025-01-01T00:01:00Z".to_string());
+        assert_eq!(
+            timeline.durations_at(now),
+            (
+                Some(Duration::from_secs(60)),
+                Some(Duration::from_secs(540))
+            )
+        );
+        assert_eq!(timeline.finished_run_time(), None);
+        timeline.terminated_at = Some("2025-01-01T00:03:30Z".to_string());
+        assert_eq!(timeline.finished_run_time(), Some(Duration::from_secs(150)));
+        assert_eq!(timeline.total_time(), Some(Duration::from_secs(210)));
+    }
+}
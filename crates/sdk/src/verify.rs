@@ -5,7 +5,10 @@ use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{API_KEY_HEADER, AxiomSdk, ProgressCallback, add_cli_version_header, get_config_id};
+use crate::{
+    API_KEY_HEADER, AxiomSdk, ClientError, ProgressCallback, add_cli_version_header, get_config_id,
+    poll_sleep, start_wait,
+};
 
 const VERIFICATION_POLLING_INTERVAL_SECS: u64 = 10;
 
@@ -189,9 +192,7 @@ impl AxiomSdk {
             let verify_status = serde_json::from_value(response_json)?;
             Ok(verify_status)
         } else if response.status().is_client_error() {
-            let status = response.status();
-            let error_text = response.text()?;
-            Err(eyre::eyre!("Client error ({}): {}", status, error_text))
+            Err(ClientError::from_response(response)?.into())
         } else {
             Err(eyre::eyre!(
                 "Status request failed with status: {}",
@@ -264,6 +265,7 @@ impl AxiomSdk {
     where
         F: Fn() -> Result<VerifyStatus>,
     {
+        let _wait = start_wait();
         use std::time::Duration;
 
         let mut spinner_started = false;
@@ -294,6 +296,13 @@ impl AxiomSdk {
                     callback.on_field("Proof Type", &verify_status.proof_type.to_uppercase());
                     callback.on_field("Created At", &verify_status.created_at);
 
+                    self.hooks.run(
+                        "verification",
+                        &verify_status.id,
+                        &verify_status.result,
+                        &verify_status,
+                        callback,
+                    );
                     return Ok(());
                 }
                 "failed" => {
@@ -314,6 +323,13 @@ impl AxiomSdk {
                     callback.on_field("Proof Type", &verify_status.proof_type.to_uppercase());
                     callback.on_field("Created At", &verify_status.created_at);
 
+                    self.hooks.run(
+                        "verification",
+                        &verify_status.id,
+                        &verify_status.result,
+                        &verify_status,
+                        callback,
+                    );
                     eyre::bail!("Proof verification failed");
                 }
                 "processing" => {
@@ -321,7 +337,10 @@ impl AxiomSdk {
                         callback.on_spinner_start("Verifying proof");
                         spinner_started = true;
                     }
-                    std::thread::sleep(Duration::from_secs(VERIFICATION_POLLING_INTERVAL_SECS));
+                    poll_sleep(
+                        Duration::from_secs(VERIFICATION_POLLING_INTERVAL_SECS),
+                        callback,
+                    )?;
                 }
                 _ => {
                     let status_message = format!("Verification status: {}", verify_status.result);
@@ -331,7 +350,10 @@ impl AxiomSdk {
                     } else {
                         callback.on_progress_update_message(&status_message);
                     }
-                    std::thread::sleep(Duration::from_secs(VERIFICATION_POLLING_INTERVAL_SECS));
+                    poll_sleep(
+                        Duration::from_secs(VERIFICATION_POLLING_INTERVAL_SECS),
+                        callback,
+                    )?;
                 }
             }
         }
@@ -343,6 +365,7 @@ impl AxiomSdk {
         verify_id: &str,
         callback: &dyn ProgressCallback,
     ) -> Result<()> {
+        let _wait = start_wait();
         use std::time::Duration;
 
         let mut spinner_started = false;
@@ -373,6 +396,13 @@ impl AxiomSdk {
                     callback.on_field("Proof Type", &verify_status.proof_type.to_uppercase());
                     callback.on_field("Created At", &verify_status.created_at);
 
+                    self.hooks.run(
+                        "verification",
+                        &verify_status.id,
+                        &verify_status.result,
+                        &verify_status,
+                        callback,
+                    );
                     return Ok(());
                 }
                 "failed" => {
@@ -393,6 +423,13 @@ impl AxiomSdk {
                     callback.on_field("Proof Type", &verify_status.proof_type.to_uppercase());
                     callback.on_field("Created At", &verify_status.created_at);
 
+                    self.hooks.run(
+                        "verification",
+                        &verify_status.id,
+                        &verify_status.result,
+                        &verify_status,
+                        callback,
+                    );
                     eyre::bail!("Proof verification failed");
                 }
                 "processing" => {
@@ -400,7 +437,10 @@ impl AxiomSdk {
                         callback.on_spinner_start("Verifying proof");
                         spinner_started = true;
                     }
-                    std::thread::sleep(Duration::from_secs(VERIFICATION_POLLING_INTERVAL_SECS));
+                    poll_sleep(
+                        Duration::from_secs(VERIFICATION_POLLING_INTERVAL_SECS),
+                        callback,
+                    )?;
                 }
                 _ => {
                     let status_message = format!("Verification status: {}", verify_status.result);
@@ -410,7 +450,10 @@ impl AxiomSdk {
                     } else {
                         callback.on_progress_update_message(&status_message);
                     }
-                    std::thread::sleep(Duration::from_secs(VERIFICATION_POLLING_INTERVAL_SECS));
+                    poll_sleep(
+                        Duration::from_secs(VERIFICATION_POLLING_INTERVAL_SECS),
+                        callback,
+                    )?;
                 }
             }
         }